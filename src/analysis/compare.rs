@@ -1,26 +1,35 @@
+use crate::stats::bivariate::Data;
+use crate::stats::hierarchical;
 use crate::stats::univariate::Sample;
-use crate::stats::univariate::{self, mixed};
-use crate::stats::Distribution;
+use crate::stats::univariate::{self, effect_size, jackknife, mixed, nonparametric};
+use crate::stats::{Distribution, Tails};
 
 use crate::benchmark::BenchmarkConfig;
 use crate::error::Result;
 use crate::estimate::{
-    build_change_estimates, ChangeDistributions, ChangeEstimates, ChangePointEstimates, Estimates,
+    build_change_estimates, build_estimate, ChangeDistributions, ChangeEstimates,
+    ChangePointEstimates, ChangeProbabilities, EffectSizes, Equivalence, Estimates, HypothesisTest,
 };
 use crate::measurement::Measurement;
 use crate::report::BenchmarkId;
-use crate::{fs, Criterion, SavedSample};
+use crate::{
+    fs, ComparisonReport, ComparisonTest, ConfidenceIntervalMethod, Criterion, SavedSample,
+    TrackedStatistic,
+};
 
 // Common comparison procedure
 #[allow(clippy::type_complexity)]
 pub(crate) fn common<M: Measurement>(
     id: &BenchmarkId,
     avg_times: &Sample<f64>,
+    runs: &[usize],
+    noise_threshold: f64,
     config: &BenchmarkConfig,
     criterion: &Criterion<M>,
 ) -> Result<(
+    Option<ComparisonTest>,
     f64,
-    Distribution<f64>,
+    Option<(f64, Distribution<f64>)>,
     ChangeEstimates,
     ChangeDistributions,
     Vec<f64>,
@@ -32,7 +41,15 @@ pub(crate) fn common<M: Measurement>(
     sample_file.push(id.as_directory_name());
     sample_file.push(&criterion.baseline_directory);
     sample_file.push("sample.json");
-    let sample: SavedSample = fs::load(&sample_file)?;
+    let mut sample: SavedSample = fs::load(&sample_file)?;
+    if config.exclude_severe_outliers {
+        let base_avg_times = super::average_times(&sample);
+        let labeled_sample = config
+            .outlier_detection
+            .classify(Sample::new(&base_avg_times));
+        sample = super::without_severe_outliers(&sample, &labeled_sample);
+    }
+    let base_runs = sample.runs();
     let SavedSample { iters, times, .. } = sample;
 
     let mut estimates_file = criterion.output_directory.clone();
@@ -52,13 +69,62 @@ pub(crate) fn common<M: Measurement>(
     change_dir.push(id.as_directory_name());
     change_dir.push("change");
     fs::mkdirp(&change_dir)?;
-    let (t_statistic, t_distribution) = t_test(avg_times, base_avg_time_sample, config);
+    // The t distribution is only bootstrapped if the t-test decides the verdict
+    let t_test = match (config.tracked_statistic, config.comparison_test) {
+        (TrackedStatistic::Mean, ComparisonTest::TTest) => Some(t_test(
+            avg_times,
+            runs,
+            base_avg_time_sample,
+            &base_runs,
+            config.nresamples,
+        )),
+        _ => None,
+    };
+
+    let (mut estimates, relative_distributions, tracked_p_value) = estimates(
+        avg_times,
+        runs,
+        base_avg_time_sample,
+        &base_runs,
+        noise_threshold,
+        config,
+    );
+    // The comparison tests only compare means, so any other statistic is tested with the
+    // bootstrap distribution of its change
+    let (test, p_value) = match config.tracked_statistic {
+        TrackedStatistic::Mean => (
+            Some(config.comparison_test),
+            p_value(
+                avg_times,
+                runs,
+                base_avg_time_sample,
+                &base_runs,
+                t_test.as_ref(),
+                config,
+            ),
+        ),
+        _ => (None, tracked_p_value),
+    };
+    estimates.test = Some(HypothesisTest {
+        statistic: config.tracked_statistic,
+        test,
+        p_value,
+        correction: None,
+        adjusted_p_value: None,
+    });
+    estimates.rng_seed = criterion.rng_seed;
 
-    let (estimates, relative_distributions) =
-        estimates(id, avg_times, base_avg_time_sample, config, criterion);
+    {
+        log_if_err!({
+            let mut estimates_path = change_dir;
+            estimates_path.push("estimates.json");
+            fs::save(&estimates, &estimates_path)
+        });
+    }
     Ok((
-        t_statistic,
-        t_distribution,
+        test,
+        p_value,
+        t_test,
         estimates,
         relative_distributions,
         iters,
@@ -68,20 +134,105 @@ pub(crate) fn common<M: Measurement>(
     ))
 }
 
-// Performs a two sample t-test
-fn t_test(
+// Computes the p-value of the comparison using the configured hypothesis test, given the t
+// statistic and its distribution if the t-test was run. The measurements of one run aren't
+// independent of each other, so if both samples were taken in several runs, the nonparametric
+// tests rank or permute the means of the runs rather than the measurements.
+fn p_value(
     avg_times: &Sample<f64>,
+    runs: &[usize],
     base_avg_times: &Sample<f64>,
+    base_runs: &[usize],
+    t_test: Option<&(f64, Distribution<f64>)>,
     config: &BenchmarkConfig,
-) -> (f64, Distribution<f64>) {
-    let nresamples = config.nresamples;
+) -> f64 {
+    let (run_means, base_run_means);
+    let (times, base_times) = if runs.len() > 1 && base_runs.len() > 1 {
+        run_means = means_of_runs(avg_times, runs);
+        base_run_means = means_of_runs(base_avg_times, base_runs);
+        (Sample::new(&run_means), Sample::new(&base_run_means))
+    } else {
+        if config.comparison_test != ComparisonTest::TTest
+            && (runs.len() > 1 || base_runs.len() > 1)
+        {
+            eprintln!(
+                "\nWarning: The {} p-value doesn't allow for the variance between runs, since \
+                 that needs several runs of both the new sample and the baseline. It may be too \
+                 small.",
+                config.comparison_test.name()
+            );
+        }
+        (avg_times, base_avg_times)
+    };
 
-    let t_statistic = avg_times.t(base_avg_times);
-    let t_distribution = elapsed!(
-        "Bootstrapping the T distribution",
-        mixed::bootstrap(avg_times, base_avg_times, nresamples, |a, b| (a.t(b),))
-    )
-    .0;
+    match (config.comparison_test, t_test) {
+        (ComparisonTest::TTest, Some((t_statistic, t_distribution))) => {
+            t_distribution.p_value(*t_statistic, &Tails::Two)
+        }
+        (ComparisonTest::TTest, None) => unreachable!("The t-test was selected but not run"),
+        (ComparisonTest::MannWhitneyU, _) => nonparametric::mann_whitney_u(times, base_times).1,
+        (ComparisonTest::Permutation, _) => elapsed!(
+            "Running the permutation test",
+            nonparametric::permutation_test(times, base_times, config.nresamples)
+        ),
+    }
+}
+
+// The mean time per iteration of each run
+fn means_of_runs(avg_times: &Sample<f64>, runs: &[usize]) -> Vec<f64> {
+    let mut start = 0;
+    runs.iter()
+        .map(|&len| {
+            let run = &avg_times[start..start + len];
+            start += len;
+            run.iter().sum::<f64>() / len as f64
+        })
+        .collect()
+}
+
+// Performs a two sample t-test. If either sample was taken in several runs, the variance between
+// runs is included in both the statistic and its distribution.
+fn t_test(
+    avg_times: &Sample<f64>,
+    runs: &[usize],
+    base_avg_times: &Sample<f64>,
+    base_runs: &[usize],
+    nresamples: usize,
+) -> (f64, Distribution<f64>) {
+    let (t_statistic, t_distribution) = if runs.len() > 1 || base_runs.len() > 1 {
+        // Under the null hypothesis both samples have the same mean, so the distribution of the
+        // statistic is estimated by resampling the runs of each sample around a common mean.
+        let centered = |times: &Sample<f64>| {
+            let mean = times.mean();
+            times.iter().map(|&time| time - mean).collect::<Vec<_>>()
+        };
+        let (centered, base_centered) = (centered(avg_times), centered(base_avg_times));
+        let t_distribution = elapsed!(
+            "Bootstrapping the T distribution across runs",
+            hierarchical::bootstrap2(runs, base_runs, nresamples, |a, b| {
+                let a_times = a.iter().map(|&i| centered[i]).collect::<Vec<_>>();
+                let b_times = b.iter().map(|&i| base_centered[i]).collect::<Vec<_>>();
+                (hierarchical_t(
+                    &a_times,
+                    &resampled_runs(a, runs),
+                    &b_times,
+                    &resampled_runs(b, base_runs),
+                ),)
+            })
+        )
+        .0;
+        (
+            hierarchical_t(avg_times, runs, base_avg_times, base_runs),
+            t_distribution,
+        )
+    } else {
+        let t_distribution = elapsed!(
+            "Bootstrapping the T distribution",
+            mixed::bootstrap(avg_times, base_avg_times, nresamples, |a, b| (a.t(b),))
+        )
+        .0;
+        (avg_times.t(base_avg_times), t_distribution)
+    };
 
     // HACK: Filter out non-finite numbers, which can happen sometimes when sample size is very small.
     // Downstream code doesn't like non-finite values here.
@@ -97,14 +248,72 @@ fn t_test(
     (t_statistic, t_distribution)
 }
 
+// The t statistic of two samples taken in runs of the given lengths
+fn hierarchical_t(times: &[f64], runs: &[usize], base_times: &[f64], base_runs: &[usize]) -> f64 {
+    let (mean, variance) = mean_and_variance_of_mean(times, runs);
+    let (base_mean, base_variance) = mean_and_variance_of_mean(base_times, base_runs);
+
+    (mean - base_mean) / (variance + base_variance).sqrt()
+}
+
+// The mean of a sample taken in runs of the given lengths, along with the variance of that mean.
+// With several runs, the variance is estimated from the spread of the run means, so that it
+// includes the variance between runs rather than treating every measurement as independent.
+fn mean_and_variance_of_mean(times: &[f64], runs: &[usize]) -> (f64, f64) {
+    let mean_of = |times: &[f64]| times.iter().sum::<f64>() / times.len() as f64;
+    let variance_of = |values: &[f64], mean: f64| {
+        values.iter().map(|&v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+    };
+
+    let mean = mean_of(times);
+    if runs.len() > 1 {
+        let mut start = 0;
+        let run_means = runs
+            .iter()
+            .map(|&len| {
+                let run_mean = mean_of(&times[start..start + len]);
+                start += len;
+                run_mean
+            })
+            .collect::<Vec<_>>();
+        let run_mean = mean_of(&run_means);
+        (mean, variance_of(&run_means, run_mean) / runs.len() as f64)
+    } else {
+        (mean, variance_of(times, mean) / times.len() as f64)
+    }
+}
+
+// The run lengths of a hierarchical resample of a sample taken in runs of the given lengths. Runs
+// are drawn whole, so each run of the resample starts with an index from the run it was drawn
+// from and has the same length.
+fn resampled_runs(indices: &[usize], runs: &[usize]) -> Vec<usize> {
+    let ends = runs
+        .iter()
+        .scan(0, |end, &len| {
+            *end += len;
+            Some(*end)
+        })
+        .collect::<Vec<_>>();
+
+    let mut resampled = Vec::with_capacity(runs.len());
+    let mut position = 0;
+    while position < indices.len() {
+        let len = runs[ends.partition_point(|&end| end <= indices[position])];
+        resampled.push(len);
+        position += len;
+    }
+    resampled
+}
+
 // Estimates the relative change in the statistics of the population
-fn estimates<M: Measurement>(
-    id: &BenchmarkId,
+fn estimates(
     avg_times: &Sample<f64>,
+    runs: &[usize],
     base_avg_times: &Sample<f64>,
+    base_runs: &[usize],
+    noise_threshold: f64,
     config: &BenchmarkConfig,
-    criterion: &Criterion<M>,
-) -> (ChangeEstimates, ChangeDistributions) {
+) -> (ChangeEstimates, ChangeDistributions, f64) {
     fn stats(a: &Sample<f64>, b: &Sample<f64>) -> (f64, f64) {
         (
             a.mean() / b.mean() - 1.,
@@ -115,10 +324,23 @@ fn estimates<M: Measurement>(
     let cl = config.confidence_level;
     let nresamples = config.nresamples;
 
-    let (dist_mean, dist_median) = elapsed!(
-        "Bootstrapping the relative statistics",
-        univariate::bootstrap(avg_times, base_avg_times, nresamples, stats)
-    );
+    // If either sample was taken in several runs, the variance between runs has to be included
+    // in the relative statistics too.
+    let (dist_mean, dist_median) = if runs.len() > 1 || base_runs.len() > 1 {
+        elapsed!(
+            "Bootstrapping the relative statistics across runs",
+            hierarchical::bootstrap2(runs, base_runs, nresamples, |a, b| {
+                let a = a.iter().map(|&i| avg_times[i]).collect::<Vec<_>>();
+                let b = b.iter().map(|&i| base_avg_times[i]).collect::<Vec<_>>();
+                stats(Sample::new(&a), Sample::new(&b))
+            })
+        )
+    } else {
+        elapsed!(
+            "Bootstrapping the relative statistics",
+            univariate::bootstrap(avg_times, base_avg_times, nresamples, stats)
+        )
+    };
 
     let distributions = ChangeDistributions {
         mean: dist_mean,
@@ -128,16 +350,287 @@ fn estimates<M: Measurement>(
     let (mean, median) = stats(avg_times, base_avg_times);
     let points = ChangePointEstimates { mean, median };
 
-    let estimates = build_change_estimates(&distributions, &points, cl);
+    // The jackknife leaves out each measurement of either sample in turn
+    let accelerations = match config.confidence_interval_method {
+        ConfidenceIntervalMethod::Percentile => None,
+        ConfidenceIntervalMethod::Bca => {
+            let acceleration = |statistic: fn((f64, f64)) -> f64| {
+                let new =
+                    jackknife::leave_one_out(avg_times, |a| statistic(stats(a, base_avg_times)));
+                let base =
+                    jackknife::leave_one_out(base_avg_times, |b| statistic(stats(avg_times, b)));
+                jackknife::acceleration(&[&new, &base])
+            };
+            Some(ChangePointEstimates {
+                mean: acceleration(|(mean, _)| mean),
+                median: acceleration(|(_, median)| median),
+            })
+        }
+    };
 
-    {
-        log_if_err!({
-            let mut estimates_path = criterion.output_directory.clone();
-            estimates_path.push(id.as_directory_name());
-            estimates_path.push("change");
-            estimates_path.push("estimates.json");
-            fs::save(&estimates, &estimates_path)
-        });
+    let mut estimates = build_change_estimates(&distributions, &points, accelerations.as_ref(), cl);
+    estimates.effect_sizes = Some(EffectSizes {
+        cohens_d: effect_size::cohens_d(avg_times, runs, base_avg_times, base_runs),
+        hodges_lehmann: effect_size::hodges_lehmann(avg_times, base_avg_times),
+        probability_of_superiority: effect_size::probability_of_superiority(
+            avg_times,
+            base_avg_times,
+        ),
+    });
+
+    // The statistics requested in addition to the mean and median are bootstrapped one at a time
+    let mut tracked_distribution = None;
+    for statistic in config.extra_statistics() {
+        let change =
+            |a: &Sample<f64>, b: &Sample<f64>| statistic.evaluate(a) / statistic.evaluate(b) - 1.;
+        let (distribution,) = if runs.len() > 1 || base_runs.len() > 1 {
+            elapsed!(
+                format!("Bootstrapping the relative {} across runs", statistic),
+                hierarchical::bootstrap2(runs, base_runs, nresamples, |a, b| {
+                    let a = a.iter().map(|&i| avg_times[i]).collect::<Vec<_>>();
+                    let b = b.iter().map(|&i| base_avg_times[i]).collect::<Vec<_>>();
+                    (change(Sample::new(&a), Sample::new(&b)),)
+                })
+            )
+        } else {
+            elapsed!(
+                format!("Bootstrapping the relative {}", statistic),
+                univariate::bootstrap(avg_times, base_avg_times, nresamples, |a, b| {
+                    (change(a, b),)
+                })
+            )
+        };
+
+        let acceleration = match config.confidence_interval_method {
+            ConfidenceIntervalMethod::Percentile => None,
+            ConfidenceIntervalMethod::Bca => {
+                let new = jackknife::leave_one_out(avg_times, |a| change(a, base_avg_times));
+                let base = jackknife::leave_one_out(base_avg_times, |b| change(avg_times, b));
+                Some(jackknife::acceleration(&[&new, &base]))
+            }
+        };
+        estimates.set_extra(
+            statistic,
+            build_estimate(
+                change(avg_times, base_avg_times),
+                &distribution,
+                acceleration,
+                cl,
+            ),
+        );
+        if statistic == config.tracked_statistic {
+            tracked_distribution = Some(distribution);
+        }
+    }
+
+    let tracked_distribution = match config.tracked_statistic {
+        TrackedStatistic::Mean => &distributions.mean,
+        TrackedStatistic::Median => &distributions.median,
+        _ => tracked_distribution.as_ref().unwrap(),
+    };
+    if config.comparison_report != ComparisonReport::PValue {
+        estimates.probabilities = Some(ChangeProbabilities::new(
+            tracked_distribution,
+            noise_threshold,
+        ));
+    }
+    if let Some(margin) = config.equivalence_margin {
+        estimates.equivalence = Some(Equivalence::new(
+            tracked_distribution,
+            margin,
+            config.significance_level,
+        ));
+    }
+    let p_value = bootstrap_p_value(tracked_distribution);
+
+    (estimates, distributions, p_value)
+}
+
+// The two-sided p-value of a change, from its bootstrap distribution: twice the fraction of the
+// distribution on the far side of zero from the bulk of it
+fn bootstrap_p_value(distribution: &Distribution<f64>) -> f64 {
+    let n = distribution.len() as f64;
+    let below = distribution.iter().filter(|&&change| change <= 0.).count() as f64 / n;
+    let above = distribution.iter().filter(|&&change| change >= 0.).count() as f64 / n;
+    (2. * below.min(above)).min(1.)
+}
+
+// Paired comparison procedure, for samples whose measurements were interleaved. The i-th
+// measurement of `avg_times` is paired with the i-th measurement of `reference_avg_times`.
+pub(crate) fn paired(
+    avg_times: &Sample<f64>,
+    reference_avg_times: &Sample<f64>,
+    config: &BenchmarkConfig,
+) -> (f64, Distribution<f64>, ChangeEstimates) {
+    fn stats(data: Data<'_, f64, f64>) -> (f64, f64) {
+        let (reference, times) = (data.x(), data.y());
+        (
+            times.mean() / reference.mean() - 1.,
+            times.percentiles().median() / reference.percentiles().median() - 1.,
+        )
+    }
+
+    let cl = config.confidence_level;
+    let nresamples = config.nresamples;
+
+    let differences = avg_times
+        .iter()
+        .zip(reference_avg_times.iter())
+        .map(|(&time, &reference)| time - reference)
+        .collect::<Vec<_>>();
+    let differences = Sample::new(&differences);
+    let t_statistic = paired_t(differences);
+
+    // Under the null hypothesis the differences have zero mean, so the distribution of the
+    // statistic is estimated by resampling the centered differences.
+    let mean_difference = differences.mean();
+    let centered = differences
+        .iter()
+        .map(|&difference| difference - mean_difference)
+        .collect::<Vec<_>>();
+    let t_distribution = elapsed!(
+        "Bootstrapping the paired T distribution",
+        Sample::new(&centered).bootstrap(nresamples, |d| (paired_t(d),))
+    )
+    .0;
+
+    // HACK: Filter out non-finite numbers, as in the unpaired t-test.
+    let t_distribution = Distribution::from(
+        t_distribution
+            .iter()
+            .filter(|a| a.is_finite())
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_boxed_slice(),
+    );
+
+    // Resampling pairs of measurements (rather than each sample independently) preserves the
+    // pairing in the relative estimates.
+    let data = Data::new(reference_avg_times.as_ref(), avg_times.as_ref());
+    let (dist_mean, dist_median) = elapsed!(
+        "Bootstrapping the paired relative statistics",
+        data.bootstrap(nresamples, stats)
+    );
+    let distributions = ChangeDistributions {
+        mean: dist_mean,
+        median: dist_median,
+    };
+
+    let (mean, median) = stats(data);
+    let points = ChangePointEstimates { mean, median };
+
+    // The jackknife leaves out each pair of measurements in turn
+    let accelerations = match config.confidence_interval_method {
+        ConfidenceIntervalMethod::Percentile => None,
+        ConfidenceIntervalMethod::Bca => {
+            let jackknife = (0..avg_times.len())
+                .filter(|_| avg_times.len() > 2)
+                .map(|i| {
+                    let without = |values: &[f64]| {
+                        let mut rest = values[..i].to_vec();
+                        rest.extend_from_slice(&values[i + 1..]);
+                        rest
+                    };
+                    let (reference, times) = (without(reference_avg_times), without(avg_times));
+                    stats(Data::new(&reference, &times))
+                })
+                .collect::<Vec<_>>();
+            let means = jackknife.iter().map(|&(mean, _)| mean).collect::<Vec<_>>();
+            let medians = jackknife
+                .iter()
+                .map(|&(_, median)| median)
+                .collect::<Vec<_>>();
+            Some(ChangePointEstimates {
+                mean: jackknife::acceleration(&[&means]),
+                median: jackknife::acceleration(&[&medians]),
+            })
+        }
+    };
+
+    let estimates = build_change_estimates(&distributions, &points, accelerations.as_ref(), cl);
+
+    (t_statistic, t_distribution, estimates)
+}
+
+// The paired t statistic of a sample of differences
+fn paired_t(differences: &Sample<f64>) -> f64 {
+    let n = differences.len() as f64;
+    let mean = differences.mean();
+    let std_dev = differences.std_dev(Some(mean));
+
+    mean / (std_dev / n.sqrt())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{bootstrap_p_value, p_value, resampled_runs, t_test};
+    use crate::stats::univariate::Sample;
+    use crate::stats::{Distribution, Tails};
+    use crate::{ComparisonTest, Criterion};
+
+    // Four runs of 25 measurements each, with little noise within each run but the given offset
+    // between runs
+    fn runs_with_offsets(offsets: [f64; 4]) -> Vec<f64> {
+        offsets
+            .iter()
+            .flat_map(|&offset| (0..25).map(move |i| offset + (i % 5) as f64 * 0.001))
+            .collect()
+    }
+
+    #[test]
+    fn run_to_run_variance_is_not_significant() {
+        let times = runs_with_offsets([1.02, 0.99, 1.04, 1.01]);
+        let base_times = runs_with_offsets([1.00, 0.97, 1.02, 0.99]);
+        let (times, base_times) = (Sample::new(&times), Sample::new(&base_times));
+
+        // Treated as independent measurements, the shift of the mean looks highly significant
+        let (t, distribution) = t_test(times, &[100], base_times, &[100], 10_000);
+        assert!(distribution.p_value(t, &Tails::Two) < 0.01);
+
+        // But it is no bigger than the shifts between runs
+        let (t, distribution) = t_test(times, &[25; 4], base_times, &[25; 4], 10_000);
+        assert!(distribution.p_value(t, &Tails::Two) > 0.05);
+    }
+
+    #[test]
+    fn splits_resamples_into_runs() {
+        let runs = [2, 3, 1];
+        assert_eq!(
+            vec![3, 2, 3],
+            resampled_runs(&[3, 2, 4, 1, 1, 4, 4, 2], &runs)
+        );
+        assert_eq!(vec![1, 1, 1], resampled_runs(&[5, 5, 5], &runs));
+    }
+
+    #[test]
+    fn nonparametric_tests_compare_runs() {
+        let times = runs_with_offsets([1.02, 0.99, 1.04, 1.01]);
+        let base_times = runs_with_offsets([1.00, 0.97, 1.02, 0.99]);
+        let (times, base_times) = (Sample::new(&times), Sample::new(&base_times));
+
+        for test in [ComparisonTest::MannWhitneyU, ComparisonTest::Permutation] {
+            let config = Criterion::default()
+                .comparison_test(test)
+                .nresamples(1000)
+                .config;
+            let p = |runs: &[usize]| p_value(times, runs, base_times, runs, None, &config);
+            // The measurements alone make the shift look highly significant, but it is no bigger
+            // than the shifts between runs
+            assert!(p(&[100]) < 0.01);
+            assert!(p(&[25; 4]) > 0.05);
+        }
+    }
+
+    #[test]
+    fn bootstrap_p_value_counts_the_far_side_of_zero() {
+        let distribution =
+            |changes: &[f64]| Distribution::from(changes.to_vec().into_boxed_slice());
+
+        let shifted = distribution(&[-0.01, 0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.07, 0.08, 0.09]);
+        assert!((bootstrap_p_value(&shifted) - 0.2).abs() < 1e-12);
+        let centred = distribution(&[-0.02, -0.01, 0.0, 0.01, 0.02]);
+        assert_eq!(1.0, bootstrap_p_value(&centred));
+        let all_above = distribution(&[0.01, 0.02, 0.03]);
+        assert_eq!(0.0, bootstrap_p_value(&all_above));
     }
-    (estimates, distributions)
 }
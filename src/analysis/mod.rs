@@ -1,21 +1,37 @@
+use serde::Serialize;
 use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::stats::bivariate::regression::Slope;
+use crate::stats::bivariate::regression::{Line, Slope};
 use crate::stats::bivariate::Data;
-use crate::stats::univariate::outliers::tukey;
+use crate::stats::hierarchical;
+use crate::stats::rand_util;
+use crate::stats::univariate::drift::{self, Changepoint};
+use crate::stats::univariate::jackknife;
+use crate::stats::univariate::outliers::tukey::LabeledSample;
 use crate::stats::univariate::Sample;
-use crate::stats::{Distribution, Tails};
+use crate::stats::{normal, Tails};
 
 use crate::benchmark::BenchmarkConfig;
 use crate::connection::OutgoingMessage;
+use crate::correction::{self, RecordedComparison};
 use crate::estimate::{
-    build_estimates, ConfidenceInterval, Distributions, Estimate, Estimates, PointEstimates,
+    build_estimate, build_estimates, ChangeEstimates, Distributions, Estimates, PointEstimates,
 };
 use crate::fs;
+use crate::kde;
 use crate::measurement::Measurement;
-use crate::report::{BenchmarkId, Report, ReportContext};
+use crate::noise::NoiseHistory;
+use crate::report::{
+    BenchmarkId, ComparisonData, OrderDiagnostics, PowerAnalysis, Report, ReportContext,
+};
 use crate::routine::Routine;
-use crate::{Baseline, Criterion, SavedSample, Throughput};
+use crate::scheduling::{AppliedScheduling, SchedulingGuard};
+use crate::{
+    ActualSamplingMode, Baseline, ConfidenceIntervalMethod, Criterion,
+    MultipleComparisonCorrection, RegressionModel, SavedSample, Throughput,
+};
 
 macro_rules! elapsed {
     ($msg:expr, $block:expr) => {{
@@ -35,6 +51,30 @@ macro_rules! elapsed {
 
 mod compare;
 
+/// How long to pause between independent runs of a benchmark.
+const PAUSE_BETWEEN_RUNS: Duration = Duration::from_secs(1);
+
+/// The number of rounds the measurement time is divided into when sampling until a target
+/// precision is reached.
+const ROUNDS: u32 = 10;
+
+/// The number of resamples used to check the precision after each round. This only decides when
+/// to stop sampling, so it doesn't need to be as accurate as the final analysis.
+const PRECISION_CHECK_RESAMPLES: usize = 2_000;
+
+/// The smallest number of measurements on either side of a changepoint.
+const MIN_CHANGEPOINT_SEGMENT: usize = 5;
+
+/// The smallest sample size that can be configured.
+const MIN_SAMPLE_SIZE: usize = 10;
+
+/// The probability of detecting a change that the power analysis plans for.
+const POWER: f64 = 0.8;
+
+/// How large the t statistic of a changepoint has to be before it's reported. This is much
+/// larger than for a single t-test, as the changepoint is the best of many possible splits.
+const CHANGEPOINT_T: f64 = 5.0;
+
 // Common analysis procedure
 pub(crate) fn common<M: Measurement, T: ?Sized>(
     id: &BenchmarkId,
@@ -46,7 +86,298 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
     throughput: Option<Throughput>,
 ) {
     criterion.report.benchmark_start(id, report_context);
+    check_strict_baseline(id, criterion);
+
+    let sample = if let Some(baseline) = &criterion.load_baseline {
+        load_sample(id, baseline, criterion)
+    } else {
+        let guard = SchedulingGuard::apply(&criterion.scheduling);
+        let mut runs = Vec::with_capacity(config.repetitions);
+        for run in 0..config.repetitions {
+            if run > 0 {
+                thread::sleep(PAUSE_BETWEEN_RUNS);
+            }
+            let run = match config.target_precision {
+                Some(target) if !config.quick_mode => sample_until_precise(
+                    routine,
+                    id,
+                    config,
+                    criterion,
+                    report_context,
+                    parameter,
+                    target,
+                ),
+                _ => routine.sample(
+                    &criterion.measurement,
+                    id,
+                    config,
+                    criterion,
+                    report_context,
+                    parameter,
+                ),
+            };
+            runs.push(run);
+        }
+        let scheduling = guard.applied();
+        drop(guard);
+        pool_runs(runs, scheduling, criterion)
+    };
+
+    analyze(id, &sample, config, criterion, report_context, throughput);
+}
+
+/// Samples several benchmarks with their measurements interleaved, analyzes each one as usual and
+/// then compares every benchmark after the first against the first one using a paired test.
+pub(crate) fn interleaved<M: Measurement>(
+    benchmarks: &mut [(&BenchmarkId, &mut dyn Routine<M, ()>)],
+    config: &BenchmarkConfig,
+    criterion: &Criterion<M>,
+    report_context: &ReportContext,
+    throughput: Option<Throughput>,
+) {
+    for (id, _) in benchmarks.iter() {
+        criterion.report.benchmark_start(id, report_context);
+        check_strict_baseline(id, criterion);
+    }
+
+    let samples = if let Some(baseline) = &criterion.load_baseline {
+        benchmarks
+            .iter()
+            .map(|(id, _)| load_sample(id, baseline, criterion))
+            .collect::<Vec<_>>()
+    } else {
+        let guard = SchedulingGuard::apply(&criterion.scheduling);
+        let mut runs = benchmarks
+            .iter()
+            .map(|_| Vec::with_capacity(config.repetitions))
+            .collect::<Vec<_>>();
+        for run in 0..config.repetitions {
+            if run > 0 {
+                thread::sleep(PAUSE_BETWEEN_RUNS);
+            }
+            let sampled =
+                crate::routine::sample_interleaved(benchmarks, config, criterion, report_context);
+            for (benchmark_runs, sample) in runs.iter_mut().zip(sampled) {
+                benchmark_runs.push(sample);
+            }
+        }
+        let scheduling = guard.applied();
+        drop(guard);
+        runs.into_iter()
+            .map(|benchmark_runs| pool_runs(benchmark_runs, scheduling, criterion))
+            .collect::<Vec<_>>()
+    };
+
+    for ((id, _), sample) in benchmarks.iter().zip(samples.iter()) {
+        analyze(
+            id,
+            sample,
+            config,
+            criterion,
+            report_context,
+            throughput.clone(),
+        );
+    }
+
+    if criterion.connection.is_some() || benchmarks.len() < 2 {
+        return;
+    }
+
+    let reference_id = benchmarks[0].0;
+    let reference_sample = &samples[0];
+    for ((id, _), sample) in benchmarks.iter().zip(samples.iter()).skip(1) {
+        if sample.iters.len() != reference_sample.iters.len()
+            || sample.times.contains(&0.0)
+            || reference_sample.times.contains(&0.0)
+        {
+            continue;
+        }
+
+        let avg_times = average_times(sample);
+        let reference_avg_times = average_times(reference_sample);
+        reseed(criterion, PAIRED_STREAM);
+        let (t_value, t_distribution, relative_estimates) = compare::paired(
+            Sample::new(&avg_times),
+            Sample::new(&reference_avg_times),
+            config,
+        );
+        let paired = crate::report::PairedComparisonData {
+            p_value: t_distribution.p_value(t_value, &Tails::Two),
+            t_value,
+            relative_estimates,
+            significance_threshold: config.significance_level,
+            noise_threshold: config.noise_threshold,
+        };
+
+        criterion
+            .report
+            .paired_comparison(id, reference_id, report_context, &paired);
+
+        if criterion.should_save_baseline() {
+            log_if_err!({
+                let mut paired_file = criterion.output_directory.clone();
+                paired_file.push(id.as_directory_name());
+                paired_file.push("new");
+                paired_file.push("paired.json");
+                fs::save(
+                    &SavedPairedComparison {
+                        reference: reference_id.as_title().to_owned(),
+                        t_value: paired.t_value,
+                        p_value: paired.p_value,
+                        relative_estimates: paired.relative_estimates,
+                    },
+                    &paired_file,
+                )
+            });
+        }
+    }
+}
+
+/// The result of a paired comparison, as saved to `paired.json`.
+#[derive(Debug, Serialize)]
+struct SavedPairedComparison {
+    reference: String,
+    t_value: f64,
+    p_value: f64,
+    relative_estimates: ChangeEstimates,
+}
+
+// Samples a benchmark in rounds until the confidence interval of the typical estimate is narrower
+// than the target precision, or the measurement time has been used up. Only the time spent
+// sampling counts towards the measurement time, not the precision checks between rounds.
+fn sample_until_precise<M: Measurement, T: ?Sized>(
+    routine: &mut dyn Routine<M, T>,
+    id: &BenchmarkId,
+    config: &BenchmarkConfig,
+    criterion: &Criterion<M>,
+    report_context: &ReportContext,
+    parameter: &T,
+    target: f64,
+) -> (ActualSamplingMode, Box<[f64]>, Box<[f64]>) {
+    let met = routine.warm_up_estimate(
+        &criterion.measurement,
+        id,
+        config,
+        criterion,
+        report_context,
+        parameter,
+    );
+
+    let n = config.sample_size as u64;
+    let round_time = config.measurement_time / ROUNDS;
+    let sampling_mode =
+        config
+            .sampling_mode
+            .choose_sampling_mode(met, n, round_time.as_nanos() as f64);
+    let m_iters = sampling_mode.iteration_counts(met, n, &round_time);
+
+    // The measurement is reported once, as if every round were needed
+    let all_rounds = m_iters.repeat(ROUNDS as usize);
+    crate::routine::report_measurement_start(id, criterion, report_context, met, &all_rounds);
+
+    // The precision checks use a cheaper analysis than the final one
+    let check_config = BenchmarkConfig {
+        nresamples: PRECISION_CHECK_RESAMPLES.min(config.nresamples),
+        confidence_interval_method: ConfidenceIntervalMethod::Percentile,
+        ..config.clone()
+    };
+
+    let mut sampling_time = Duration::ZERO;
+    let mut iters = vec![];
+    let mut times = vec![];
+    loop {
+        let round_start = Instant::now();
+        let (round_iters, round_times) = routine.sample_batch(criterion, &m_iters, parameter);
+        sampling_time += round_start.elapsed();
+        iters.extend_from_slice(&round_iters);
+        times.extend_from_slice(&round_times);
 
+        if sampling_time >= config.measurement_time || times.contains(&0.0) {
+            break;
+        }
+
+        let data = Data::new(&iters, &times);
+        let avg_times = data
+            .x()
+            .iter()
+            .zip(data.y().iter())
+            .map(|(&iters, &elapsed)| elapsed / iters)
+            .collect::<Vec<_>>();
+        let (mut distributions, mut estimates) =
+            estimates(Sample::new(&avg_times), &[avg_times.len()], &check_config);
+        if sampling_mode.is_linear() {
+            regression(
+                &data,
+                &[avg_times.len()],
+                &check_config,
+                &mut distributions,
+                &mut estimates,
+            );
+        }
+        let typical = estimates.typical();
+        let precision = (typical.confidence_interval.upper_bound
+            - typical.confidence_interval.lower_bound)
+            / 2.0
+            / typical.point_estimate;
+        info!(
+            "Relative precision of {} after {} samples is {:.4}",
+            id.as_title(),
+            avg_times.len(),
+            precision
+        );
+        if precision <= target {
+            break;
+        }
+    }
+
+    (
+        sampling_mode,
+        iters.into_boxed_slice(),
+        times.into_boxed_slice(),
+    )
+}
+
+// Combines the samples from one or more independent runs of a benchmark into one sample
+#[allow(clippy::type_complexity)]
+fn pool_runs<M: Measurement>(
+    runs: Vec<(ActualSamplingMode, Box<[f64]>, Box<[f64]>)>,
+    scheduling: AppliedScheduling,
+    criterion: &Criterion<M>,
+) -> SavedSample {
+    // With Auto sampling, runs may choose different modes. Regression is only meaningful if
+    // every run varied the iteration counts.
+    let sampling_mode = if runs.iter().all(|(mode, _, _)| *mode == runs[0].0) {
+        runs[0].0.clone()
+    } else if runs.iter().all(|(mode, _, _)| mode.is_linear()) {
+        ActualSamplingMode::Linear
+    } else {
+        ActualSamplingMode::Flat
+    };
+    let run_lengths = if runs.len() > 1 {
+        runs.iter().map(|(_, iters, _)| iters.len()).collect()
+    } else {
+        vec![]
+    };
+
+    let mut iters = vec![];
+    let mut times = vec![];
+    for (_, run_iters, run_times) in &runs {
+        iters.extend_from_slice(run_iters);
+        times.extend_from_slice(run_times);
+    }
+
+    SavedSample {
+        sampling_mode,
+        iters,
+        times,
+        scheduling,
+        environment: Some(crate::system_environment().clone()),
+        shuffle_seed: criterion.shuffle_seed,
+        run_lengths,
+    }
+}
+
+fn check_strict_baseline<M: Measurement>(id: &BenchmarkId, criterion: &Criterion<M>) {
     if let Baseline::CompareStrict = criterion.baseline {
         if !base_dir_exists(
             id,
@@ -59,44 +390,116 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
             );
         }
     }
+}
 
-    let (sampling_mode, iters, times);
-    if let Some(baseline) = &criterion.load_baseline {
-        let mut sample_path = criterion.output_directory.clone();
-        sample_path.push(id.as_directory_name());
-        sample_path.push(baseline);
-        sample_path.push("sample.json");
-        let loaded = fs::load::<SavedSample, _>(&sample_path);
+fn load_sample<M: Measurement>(
+    id: &BenchmarkId,
+    baseline: &str,
+    criterion: &Criterion<M>,
+) -> SavedSample {
+    let mut sample_path = criterion.output_directory.clone();
+    sample_path.push(id.as_directory_name());
+    sample_path.push(baseline);
+    sample_path.push("sample.json");
+    let loaded = fs::load::<SavedSample, _>(&sample_path);
 
-        match loaded {
-            Err(err) => panic!(
-                "Baseline '{base}' must exist before it can be loaded; try --save-baseline {base}. Error: {err}",
-                base = baseline, err = err
-            ),
-            Ok(samples) => {
-                sampling_mode = samples.sampling_mode;
-                iters = samples.iters.into_boxed_slice();
-                times = samples.times.into_boxed_slice();
-            }
-        }
-    } else {
-        let sample = routine.sample(
-            &criterion.measurement,
-            id,
-            config,
-            criterion,
-            report_context,
-            parameter,
-        );
-        sampling_mode = sample.0;
-        iters = sample.1;
-        times = sample.2;
+    match loaded {
+        Err(err) => panic!(
+            "Baseline '{base}' must exist before it can be loaded; try --save-baseline {base}. Error: {err}",
+            base = baseline, err = err
+        ),
+        Ok(samples) => samples,
+    }
+}
+
+fn average_times(sample: &SavedSample) -> Vec<f64> {
+    sample
+        .iters
+        .iter()
+        .zip(sample.times.iter())
+        .map(|(&iters, &elapsed)| elapsed / iters)
+        .collect()
+}
 
+// Removes the measurements labeled as severe outliers from the sample. The run lengths are
+// recomputed, dropping any run that has no measurements left.
+pub(crate) fn without_severe_outliers(
+    sample: &SavedSample,
+    labeled_sample: &LabeledSample<'_, f64>,
+) -> SavedSample {
+    let keep = labeled_sample
+        .iter()
+        .map(|(_, label)| !label.is_severe())
+        .collect::<Vec<_>>();
+
+    let filter = |values: &[f64]| {
+        values
+            .iter()
+            .zip(&keep)
+            .filter(|(_, &keep)| keep)
+            .map(|(&value, _)| value)
+            .collect::<Vec<_>>()
+    };
+
+    let mut start = 0;
+    let run_lengths = sample
+        .run_lengths
+        .iter()
+        .map(|&len| {
+            let kept = keep[start..start + len]
+                .iter()
+                .filter(|&&keep| keep)
+                .count();
+            start += len;
+            kept
+        })
+        .filter(|&len| len > 0)
+        .collect();
+
+    SavedSample {
+        sampling_mode: sample.sampling_mode.clone(),
+        iters: filter(&sample.iters),
+        times: filter(&sample.times),
+        scheduling: sample.scheduling,
+        environment: sample.environment.clone(),
+        shuffle_seed: sample.shuffle_seed,
+        run_lengths,
+    }
+}
+
+// The streams of the random number generator used by each stage of the analysis
+const ESTIMATES_STREAM: u64 = 0;
+const COMPARISON_STREAM: u64 = 1;
+const PAIRED_STREAM: u64 = 2;
+pub(crate) const REFERENCE_STREAM: u64 = 3;
+
+// Restarts the random number generator for a stage of the analysis if a seed was given, so that
+// analyzing the same sample again gives the same results
+pub(crate) fn reseed<M: Measurement>(criterion: &Criterion<M>, stream: u64) {
+    if let Some(seed) = criterion.rng_seed {
+        rand_util::reseed(seed, stream);
+    }
+}
+
+// Analyzes a sample, compares it against the baseline and reports the results
+fn analyze<M: Measurement>(
+    id: &BenchmarkId,
+    sample: &SavedSample,
+    config: &BenchmarkConfig,
+    criterion: &Criterion<M>,
+    report_context: &ReportContext,
+    throughput: Option<Throughput>,
+) {
+    let sampling_mode = &sample.sampling_mode;
+    let iters = &sample.iters;
+    let times = &sample.times;
+
+    if criterion.load_baseline.is_none() {
         if let Some(conn) = &criterion.connection {
             conn.send(&OutgoingMessage::MeasurementComplete {
                 id: id.into(),
-                iters: &iters,
-                times: &times,
+                iters,
+                times,
                 plot_config: (&report_context.plot_config).into(),
                 sampling_method: sampling_mode.into(),
                 benchmark_config: config.into(),
@@ -121,11 +524,7 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
         return;
     }
 
-    let avg_times = iters
-        .iter()
-        .zip(times.iter())
-        .map(|(&iters, &elapsed)| elapsed / iters)
-        .collect::<Vec<f64>>();
+    let avg_times = average_times(sample);
     let avg_times = Sample::new(&avg_times);
 
     if criterion.should_save_baseline() {
@@ -137,8 +536,7 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
         });
     }
 
-    let data = Data::new(&iters, &times);
-    let labeled_sample = tukey::classify(avg_times);
+    let labeled_sample = config.outlier_detection.classify(avg_times);
     if criterion.should_save_baseline() {
         log_if_err!({
             let mut tukey_file = criterion.output_directory.to_owned();
@@ -148,12 +546,41 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
             fs::save(&labeled_sample.fences(), &tukey_file)
         });
     }
-    let (mut distributions, mut estimates) = estimates(avg_times, config);
+
+    // The estimates and comparisons use the sample without its severe outliers if requested, but
+    // the diagnostics and plots always use the full sample
+    let filtered_sample = if config.exclude_severe_outliers {
+        Some(without_severe_outliers(sample, &labeled_sample))
+    } else {
+        None
+    };
+    let estimation_sample = filtered_sample.as_ref().unwrap_or(sample);
+    let estimation_avg_times = average_times(estimation_sample);
+    let estimation_avg_times = Sample::new(&estimation_avg_times);
+    let data = Data::new(&estimation_sample.iters, &estimation_sample.times);
+
+    let runs = estimation_sample.runs();
+    reseed(criterion, ESTIMATES_STREAM);
+    let (mut distributions, mut estimates) = estimates(estimation_avg_times, &runs, config);
     if sampling_mode.is_linear() {
-        let (distribution, slope) = regression(&data, config);
+        regression(&data, &runs, config, &mut distributions, &mut estimates);
+    }
+    extra_estimates(estimation_avg_times, &runs, config, &mut estimates);
+    estimates.rng_seed = criterion.rng_seed;
+    if filtered_sample.is_some() {
+        estimates.excluded_outliers = Some(iters.len() - estimation_sample.iters.len());
+    }
 
-        estimates.slope = Some(slope);
-        distributions.slope = Some(distribution);
+    if runs.len() > 1 && criterion.should_save_baseline() {
+        let run_estimates =
+            run_estimates(&data, estimation_avg_times, &runs, sampling_mode, config);
+        log_if_err!({
+            let mut runs_file = criterion.output_directory.clone();
+            runs_file.push(id.as_directory_name());
+            runs_file.push("new");
+            runs_file.push("runs.json");
+            fs::save(&run_estimates, &runs_file)
+        });
     }
 
     if criterion.should_save_baseline() {
@@ -162,14 +589,7 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
             sample_file.push(id.as_directory_name());
             sample_file.push("new");
             sample_file.push("sample.json");
-            fs::save(
-                &SavedSample {
-                    sampling_mode,
-                    iters: data.x().as_ref().to_vec(),
-                    times: data.y().as_ref().to_vec(),
-                },
-                &sample_file,
-            )
+            fs::save(sample, &sample_file)
         });
         log_if_err!({
             let mut estimates_file = criterion.output_directory.clone();
@@ -180,38 +600,59 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
         });
     }
 
-    let compare_data = if base_dir_exists(
+    // The noise threshold is learned from the changes recorded before this comparison, so that a
+    // genuine change doesn't raise its own threshold
+    let mut noise_history = NoiseHistory::load(&criterion.output_directory, id);
+    let learned_noise = if config.learn_noise_threshold {
+        noise_history.threshold(config.tracked_statistic)
+    } else {
+        None
+    };
+    let noise_threshold = learned_noise.map_or(config.noise_threshold, |l| l.threshold);
+
+    let mut compare_data = if base_dir_exists(
         id,
         &criterion.baseline_directory,
         &criterion.output_directory,
     ) {
-        let result = compare::common(id, avg_times, config, criterion);
+        reseed(criterion, COMPARISON_STREAM);
+        let result = compare::common(
+            id,
+            estimation_avg_times,
+            &runs,
+            noise_threshold,
+            config,
+            criterion,
+        );
         match result {
             Ok((
-                t_value,
-                t_distribution,
+                test,
+                p_value,
+                t_test,
                 relative_estimates,
                 relative_distributions,
                 base_iter_counts,
                 base_sample_times,
                 base_avg_times,
                 base_estimates,
-            )) => {
-                let p_value = t_distribution.p_value(t_value, &Tails::Two);
-                Some(crate::report::ComparisonData {
-                    p_value,
-                    t_distribution,
-                    t_value,
-                    relative_estimates,
-                    relative_distributions,
-                    significance_threshold: config.significance_level,
-                    noise_threshold: config.noise_threshold,
-                    base_iter_counts,
-                    base_sample_times,
-                    base_avg_times,
-                    base_estimates,
-                })
-            }
+            )) => Some(ComparisonData {
+                test,
+                report: config.comparison_report,
+                statistic: config.tracked_statistic,
+                p_value,
+                correction: None,
+                t_test,
+                equivalence: relative_estimates.equivalence.clone(),
+                relative_estimates,
+                relative_distributions,
+                significance_threshold: config.significance_level,
+                noise_threshold,
+                learned_noise,
+                base_iter_counts,
+                base_sample_times,
+                base_avg_times,
+                base_estimates,
+            }),
             Err(e) => {
                 crate::error::log_error(&e);
                 None
@@ -221,13 +662,65 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
         None
     };
 
+    // Only the changes between consecutive runs measure the noise. Comparisons against a fixed
+    // baseline with `--baseline` would record the same genuine change over and over.
+    if let Some(comparison) = &compare_data {
+        if criterion.should_save_baseline() && matches!(criterion.baseline, Baseline::Save) {
+            noise_history.record(
+                config.tracked_statistic,
+                comparison.verdict_estimate().point_estimate,
+            );
+            log_if_err!(noise_history.save(&criterion.output_directory, id));
+        }
+    }
+
+    let power = power_analysis(
+        &estimates,
+        estimation_avg_times,
+        &runs,
+        sampling_mode,
+        noise_threshold,
+        config,
+    );
+    if let Some(comparison) = &mut compare_data {
+        if criterion.correction != MultipleComparisonCorrection::None {
+            // The verdict waits for the end of the run, when the p-values of all of its
+            // comparisons are corrected together
+            comparison.correction = Some(criterion.correction);
+            if let Some(test) = &mut comparison.relative_estimates.test {
+                test.correction = Some(criterion.correction);
+            }
+            let mut estimates_file = criterion.output_directory.clone();
+            estimates_file.push(id.as_directory_name());
+            estimates_file.push("change");
+            estimates_file.push("estimates.json");
+            correction::record(RecordedComparison {
+                id: id.clone(),
+                method: criterion.correction,
+                p_value: comparison.p_value,
+                change: comparison.verdict_estimate().clone(),
+                significance_threshold: comparison.significance_threshold,
+                noise_threshold: comparison.noise_threshold,
+                estimates: comparison.relative_estimates.clone(),
+                estimates_file,
+            });
+        }
+    }
+
     let measurement_data = crate::report::MeasurementData {
-        data: Data::new(&iters, &times),
+        data: Data::new(iters, times),
         avg_times: labeled_sample,
         absolute_estimates: estimates,
         distributions,
         comparison: compare_data,
         throughput,
+        modes: kde::modes(
+            avg_times,
+            report_context.plot_config.kde_kernel,
+            report_context.plot_config.kde_bandwidth,
+        ),
+        order: order_diagnostics(sample, config.noise_threshold),
+        power,
     };
 
     criterion.report.measurement_complete(
@@ -258,6 +751,142 @@ pub(crate) fn common<M: Measurement, T: ?Sized>(
     }
 }
 
+// Looks for signs that the measurements aren't independent of the order they were taken in. Each
+// run is checked on its own, so that a difference between runs isn't mistaken for a shift during
+// one. With linear sampling, a fixed overhead per sample makes the time per iteration fall as the
+// iteration count grows, so the overhead estimated by a regression over the run is taken out first.
+fn order_diagnostics(sample: &SavedSample, noise_threshold: f64) -> OrderDiagnostics {
+    let n = sample.iters.len() as f64;
+    let mut autocorrelation = 0.0;
+    let mut effective_sample_size = 0.0;
+    let mut changepoint: Option<Changepoint> = None;
+
+    let mut start = 0;
+    for len in sample.runs() {
+        let iters = &sample.iters[start..start + len];
+        let times = &sample.times[start..start + len];
+        let overhead = if sample.sampling_mode.is_linear() {
+            Line::fit(&Data::new(iters, times)).intercept
+        } else {
+            0.0
+        };
+        let per_iteration = iters
+            .iter()
+            .zip(times)
+            .map(|(&iters, &elapsed)| (elapsed - overhead) / iters)
+            .collect::<Vec<_>>();
+        let per_iteration = Sample::new(&per_iteration);
+
+        autocorrelation += drift::autocorrelation(per_iteration, 1) * len as f64 / n;
+        effective_sample_size += drift::effective_sample_size(per_iteration);
+
+        let run_changepoint =
+            drift::changepoint(per_iteration, MIN_CHANGEPOINT_SEGMENT).filter(|point| {
+                point.t.abs() > CHANGEPOINT_T
+                    && (point.mean_after / point.mean_before - 1.0).abs() > noise_threshold
+            });
+        if let Some(point) = run_changepoint {
+            if changepoint.map_or(true, |largest| point.t.abs() > largest.t.abs()) {
+                changepoint = Some(Changepoint {
+                    index: start + point.index,
+                    ..point
+                });
+            }
+        }
+
+        start += len;
+    }
+
+    // Only warn about correlations which are both significant and large enough to matter, ie.
+    // which halve the effective sample size.
+    let correlated = autocorrelation > (2.0 / n.sqrt()).max(1.0 / 3.0);
+
+    OrderDiagnostics {
+        autocorrelation,
+        effective_sample_size,
+        correlated,
+        changepoint,
+    }
+}
+
+// Estimates the smallest change in the mean that a comparison of two samples like this one can
+// detect, and the number of samples per run and measurement time needed to detect a change of the
+// target size. The baseline is assumed to be as variable as the new sample. Only the variance
+// within runs falls with the number of samples per run, so if the variance between runs is too
+// large, no number of samples will do and more runs are needed instead.
+fn power_analysis(
+    estimates: &Estimates,
+    avg_times: &Sample<f64>,
+    runs: &[usize],
+    sampling_mode: &ActualSamplingMode,
+    noise_threshold: f64,
+    config: &BenchmarkConfig,
+) -> PowerAnalysis {
+    let relative_std_error = estimates.mean.standard_error / estimates.mean.point_estimate;
+    let z = normal::quantile(1.0 - config.significance_level / 2.0) + normal::quantile(POWER);
+    let minimum_detectable_effect = z * 2f64.sqrt() * relative_std_error;
+
+    // The variance of the mean relative to the one needed to detect the target effect, split
+    // into the share between runs, which stays, and the share within them, which falls in
+    // proportion to the number of samples per run
+    let target_effect = config.target_effect.unwrap_or(noise_threshold);
+    let variance_ratio = (minimum_detectable_effect / target_effect).powi(2);
+    let between = between_run_share(avg_times, runs);
+    let sample_size = avg_times.len() / runs.len();
+    let required = (between * variance_ratio < 1.0).then(|| {
+        let n = sample_size as f64;
+        let required =
+            (n * (1.0 - between) * variance_ratio / (1.0 - between * variance_ratio)).ceil();
+        let required_sample_size = (required as usize).max(MIN_SAMPLE_SIZE);
+
+        // The smallest iteration count is kept, so that each sample is as precise as before
+        let time_ratio = sampling_mode.total_iterations(required_sample_size)
+            / sampling_mode.total_iterations(sample_size);
+        (
+            required_sample_size,
+            config.measurement_time.mul_f64(time_ratio),
+        )
+    });
+
+    PowerAnalysis {
+        power: POWER,
+        minimum_detectable_effect,
+        target_effect,
+        runs: runs.len(),
+        required,
+    }
+}
+
+// The share of the variance of the mean that comes from differences between runs, estimated by
+// comparing the variance of the means of the runs with the variance within them. Zero if the
+// sample was taken in a single run.
+fn between_run_share(avg_times: &Sample<f64>, runs: &[usize]) -> f64 {
+    if runs.len() < 2 {
+        return 0.0;
+    }
+
+    let mut means = Vec::with_capacity(runs.len());
+    let mut squares = 0.0;
+    let mut start = 0;
+    for &len in runs {
+        let run = &avg_times[start..start + len];
+        let mean = run.iter().sum::<f64>() / len as f64;
+        squares += run.iter().map(|&t| (t - mean).powi(2)).sum::<f64>();
+        means.push(mean);
+        start += len;
+    }
+
+    // The variance of the mean of a run due to the variance within runs alone
+    let per_run = (avg_times.len() / runs.len()) as f64;
+    let within = squares / (avg_times.len() - runs.len()) as f64 / per_run;
+    let between = (Sample::new(&means).var(None) - within).max(0.0);
+    if between + within > 0.0 {
+        between / (between + within)
+    } else {
+        0.0
+    }
+}
+
 fn base_dir_exists(id: &BenchmarkId, baseline: &str, output_directory: &Path) -> bool {
     let mut base_dir = output_directory.to_owned();
     base_dir.push(id.as_directory_name());
@@ -265,39 +894,98 @@ fn base_dir_exists(id: &BenchmarkId, baseline: &str, output_directory: &Path) ->
     base_dir.exists()
 }
 
-// Performs a simple linear regression on the sample
+// Fits lines through the origin and with an intercept to the sample, adding their estimates and
+// distributions to those of the other statistics
 fn regression(
     data: &Data<'_, f64, f64>,
+    runs: &[usize],
     config: &BenchmarkConfig,
-) -> (Distribution<f64>, Estimate) {
+    distributions: &mut Distributions,
+    estimates: &mut Estimates,
+) {
+    fn stats(data: &Data<'_, f64, f64>) -> (f64, f64, f64) {
+        let line = Line::fit(data);
+        (Slope::fit(data).0, line.intercept, line.slope)
+    }
+
     let cl = config.confidence_level;
 
-    let distribution = elapsed!(
-        "Bootstrapped linear regression",
-        data.bootstrap(config.nresamples, |d| (Slope::fit(&d).0,))
-    )
-    .0;
+    let (dist_slope, dist_intercept, dist_slope_with_intercept) = if runs.len() > 1 {
+        elapsed!(
+            "Bootstrapped linear regression across runs",
+            hierarchical::bootstrap(runs, config.nresamples, |indices| {
+                let xs = indices.iter().map(|&i| data.x()[i]).collect::<Vec<_>>();
+                let ys = indices.iter().map(|&i| data.y()[i]).collect::<Vec<_>>();
+                stats(&Data::new(&xs, &ys))
+            })
+        )
+    } else {
+        elapsed!(
+            "Bootstrapped linear regression",
+            data.bootstrap(config.nresamples, |d| stats(&d))
+        )
+    };
 
-    let point = Slope::fit(data);
-    let (lb, ub) = distribution.confidence_interval(config.confidence_level);
-    let se = distribution.std_dev(None);
+    let (slope, intercept, slope_with_intercept) = stats(data);
+    let accelerations = match config.confidence_interval_method {
+        ConfidenceIntervalMethod::Percentile => None,
+        ConfidenceIntervalMethod::Bca if data.x().len() < 3 => Some((0., 0., 0.)),
+        ConfidenceIntervalMethod::Bca => {
+            let (xs, ys) = (data.x(), data.y());
+            let jackknife = (0..xs.len())
+                .map(|i| {
+                    let without = |values: &[f64]| {
+                        let mut rest = values[..i].to_vec();
+                        rest.extend_from_slice(&values[i + 1..]);
+                        rest
+                    };
+                    stats(&Data::new(&without(xs), &without(ys)))
+                })
+                .collect::<Vec<_>>();
+            let acceleration = |statistic: fn(&(f64, f64, f64)) -> f64| {
+                let values = jackknife.iter().map(statistic).collect::<Vec<_>>();
+                jackknife::acceleration(&[&values])
+            };
+            Some((
+                acceleration(|fit| fit.0),
+                acceleration(|fit| fit.1),
+                acceleration(|fit| fit.2),
+            ))
+        }
+    };
 
-    (
-        distribution,
-        Estimate {
-            confidence_interval: ConfidenceInterval {
-                confidence_level: cl,
-                lower_bound: lb,
-                upper_bound: ub,
-            },
-            point_estimate: point.0,
-            standard_error: se,
-        },
-    )
+    estimates.slope = Some(build_estimate(
+        slope,
+        &dist_slope,
+        accelerations.map(|a| a.0),
+        cl,
+    ));
+    estimates.intercept = Some(build_estimate(
+        intercept,
+        &dist_intercept,
+        accelerations.map(|a| a.1),
+        cl,
+    ));
+    estimates.slope_with_intercept = Some(build_estimate(
+        slope_with_intercept,
+        &dist_slope_with_intercept,
+        accelerations.map(|a| a.2),
+        cl,
+    ));
+    estimates.regression_model = config.regression_model;
+
+    distributions.slope = Some(dist_slope);
+    distributions.intercept = Some(dist_intercept);
+    distributions.slope_with_intercept = Some(dist_slope_with_intercept);
+    distributions.regression_model = config.regression_model;
 }
 
 // Estimates the statistics of the population from the sample
-fn estimates(avg_times: &Sample<f64>, config: &BenchmarkConfig) -> (Distributions, Estimates) {
+fn estimates(
+    avg_times: &Sample<f64>,
+    runs: &[usize],
+    config: &BenchmarkConfig,
+) -> (Distributions, Estimates) {
     fn stats(sample: &Sample<f64>) -> (f64, f64, f64, f64) {
         let mean = sample.mean();
         let std_dev = sample.std_dev(Some(mean));
@@ -318,10 +1006,20 @@ fn estimates(avg_times: &Sample<f64>, config: &BenchmarkConfig) -> (Distribution
         median_abs_dev: mad,
     };
 
-    let (dist_mean, dist_stddev, dist_median, dist_mad) = elapsed!(
-        "Bootstrapping the absolute statistics.",
-        avg_times.bootstrap(nresamples, stats)
-    );
+    let (dist_mean, dist_stddev, dist_median, dist_mad) = if runs.len() > 1 {
+        elapsed!(
+            "Bootstrapping the absolute statistics across runs.",
+            hierarchical::bootstrap(runs, nresamples, |indices| {
+                let resample = indices.iter().map(|&i| avg_times[i]).collect::<Vec<_>>();
+                stats(Sample::new(&resample))
+            })
+        )
+    } else {
+        elapsed!(
+            "Bootstrapping the absolute statistics.",
+            avg_times.bootstrap(nresamples, stats)
+        )
+    };
 
     let distributions = Distributions {
         mean: dist_mean,
@@ -329,13 +1027,106 @@ fn estimates(avg_times: &Sample<f64>, config: &BenchmarkConfig) -> (Distribution
         median: dist_median,
         median_abs_dev: dist_mad,
         std_dev: dist_stddev,
+        intercept: None,
+        slope_with_intercept: None,
+        regression_model: RegressionModel::ThroughOrigin,
     };
 
-    let estimates = build_estimates(&distributions, &points, cl);
+    let accelerations = match config.confidence_interval_method {
+        ConfidenceIntervalMethod::Percentile => None,
+        ConfidenceIntervalMethod::Bca => {
+            let acceleration = |statistic: fn(&Sample<f64>) -> f64| {
+                jackknife::acceleration(&[&jackknife::leave_one_out(avg_times, statistic)])
+            };
+            Some(PointEstimates {
+                mean: acceleration(|sample| stats(sample).0),
+                std_dev: acceleration(|sample| stats(sample).1),
+                median: acceleration(|sample| stats(sample).2),
+                median_abs_dev: acceleration(|sample| stats(sample).3),
+            })
+        }
+    };
+    let estimates = build_estimates(&distributions, &points, accelerations.as_ref(), cl);
 
     (distributions, estimates)
 }
 
+// Estimates the statistics requested in addition to the mean and median. Each of them is
+// bootstrapped on its own.
+fn extra_estimates(
+    avg_times: &Sample<f64>,
+    runs: &[usize],
+    config: &BenchmarkConfig,
+    estimates: &mut Estimates,
+) {
+    for statistic in config.extra_statistics() {
+        let (distribution,) = if runs.len() > 1 {
+            elapsed!(
+                format!("Bootstrapping the {} across runs", statistic),
+                hierarchical::bootstrap(runs, config.nresamples, |indices| {
+                    let resample = indices.iter().map(|&i| avg_times[i]).collect::<Vec<_>>();
+                    (statistic.evaluate(Sample::new(&resample)),)
+                })
+            )
+        } else {
+            elapsed!(
+                format!("Bootstrapping the {}", statistic),
+                avg_times.bootstrap(config.nresamples, |sample| (statistic.evaluate(sample),))
+            )
+        };
+
+        let acceleration = match config.confidence_interval_method {
+            ConfidenceIntervalMethod::Percentile => None,
+            ConfidenceIntervalMethod::Bca => {
+                Some(jackknife::acceleration(&[&jackknife::leave_one_out(
+                    avg_times,
+                    |sample| statistic.evaluate(sample),
+                )]))
+            }
+        };
+        estimates.set_extra(
+            statistic,
+            build_estimate(
+                statistic.evaluate(avg_times),
+                &distribution,
+                acceleration,
+                config.confidence_level,
+            ),
+        );
+    }
+}
+
+// Estimates the statistics of each independent run on its own
+fn run_estimates(
+    data: &Data<'_, f64, f64>,
+    avg_times: &Sample<f64>,
+    runs: &[usize],
+    sampling_mode: &ActualSamplingMode,
+    config: &BenchmarkConfig,
+) -> Vec<Estimates> {
+    let mut start = 0;
+    runs.iter()
+        .map(|&len| {
+            let range = start..start + len;
+            start += len;
+
+            let run_avg_times = Sample::new(&avg_times[range.clone()]);
+            let (mut distributions, mut estimates) = estimates(run_avg_times, &[len], config);
+            if sampling_mode.is_linear() {
+                let run_data = Data::new(&data.x()[range.clone()], &data.y()[range]);
+                regression(
+                    &run_data,
+                    &[len],
+                    config,
+                    &mut distributions,
+                    &mut estimates,
+                );
+            }
+            estimates
+        })
+        .collect()
+}
+
 fn copy_new_dir_to_base(id: &str, baseline: &str, output_directory: &Path) {
     let root_dir = Path::new(output_directory).join(id);
     let base_dir = root_dir.join(baseline);
@@ -368,3 +1159,126 @@ fn copy_new_dir_to_base(id: &str, baseline: &str, output_directory: &Path) {
     #[cfg(feature = "csv_output")]
     try_else_return!(fs::cp(&new_dir.join("raw.csv"), &base_dir.join("raw.csv")));
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{analyze, between_run_share, order_diagnostics};
+    use crate::report::{BenchmarkId, ReportContext};
+    use crate::stats::univariate::Sample;
+    use crate::{ActualSamplingMode, Criterion, PlotConfiguration, SavedSample};
+
+    fn sample(sampling_mode: ActualSamplingMode, iters: Vec<f64>, times: Vec<f64>) -> SavedSample {
+        SavedSample {
+            sampling_mode,
+            iters,
+            times,
+            scheduling: Default::default(),
+            environment: None,
+            shuffle_seed: None,
+            run_lengths: vec![],
+        }
+    }
+
+    // A little noise which repeats every few measurements, so it doesn't trend
+    fn noise(i: usize) -> f64 {
+        [0.0, 0.3, -0.2, 0.1, -0.3, 0.2][i % 6] * 0.01
+    }
+
+    #[test]
+    fn runs_are_checked_separately() {
+        let times = (0..100)
+            .map(|i| if i < 50 { 10.0 } else { 12.0 } + noise(i))
+            .collect::<Vec<_>>();
+        let mut sample = sample(ActualSamplingMode::Flat, vec![1.0; 100], times);
+
+        let pooled = order_diagnostics(&sample, 0.01);
+        assert_eq!(50, pooled.changepoint.unwrap().index);
+
+        sample.run_lengths = vec![50, 50];
+        let order = order_diagnostics(&sample, 0.01);
+        assert!(order.changepoint.is_none());
+        assert!(!order.correlated);
+    }
+
+    #[test]
+    fn overhead_of_linear_sampling_is_not_a_trend() {
+        let iters = (1..=100).map(f64::from).collect::<Vec<_>>();
+        let times = iters
+            .iter()
+            .enumerate()
+            .map(|(i, &iters)| 100.0 + iters * (1.0 + noise(i)))
+            .collect::<Vec<_>>();
+        let order = order_diagnostics(&sample(ActualSamplingMode::Linear, iters, times), 0.01);
+        assert!(order.changepoint.is_none());
+        assert!(!order.correlated);
+    }
+
+    #[test]
+    fn offsets_between_runs_dont_fall_with_the_sample_size() {
+        let times = (0..100).map(|i| 10.0 + noise(i)).collect::<Vec<_>>();
+        assert_eq!(0.0, between_run_share(Sample::new(&times), &[100]));
+        assert!(between_run_share(Sample::new(&times), &[50, 50]) < 0.1);
+
+        let offset = (0..100)
+            .map(|i| if i < 50 { 10.0 } else { 10.5 } + noise(i))
+            .collect::<Vec<_>>();
+        assert!(between_run_share(Sample::new(&offset), &[50, 50]) > 0.9);
+    }
+
+    #[test]
+    fn time_grows_with_the_iterations_of_each_sampling_mode() {
+        assert_eq!(20.0, ActualSamplingMode::Flat.total_iterations(20));
+        assert_eq!(210.0, ActualSamplingMode::Linear.total_iterations(20));
+        let custom = ActualSamplingMode::Custom(vec![1, 3]);
+        assert_eq!(20.0, custom.total_iterations(10));
+
+        // Geometric samples cover the same range of iteration counts as linear ones, but with
+        // fewer of the larger counts
+        let geometric = ActualSamplingMode::Geometric.total_iterations(20);
+        assert!(20.0 < geometric && geometric < 210.0);
+        let ratio = 20f64.powf(1.0 / 19.0);
+        assert!((geometric - (ratio.powi(20) - 1.0) / (ratio - 1.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn seeded_analysis_is_reproducible() {
+        let id = BenchmarkId::new("group".to_owned(), Some("seeded".to_owned()), None, None);
+        let analyze_in = |dir: &Path| {
+            let criterion = Criterion::default()
+                .output_directory(dir)
+                .without_plots()
+                .nresamples(1000)
+                .rng_seed(42);
+            let report_context = ReportContext {
+                output_directory: dir.to_owned(),
+                plot_config: PlotConfiguration::default(),
+            };
+            // The baseline, and then a slower sample compared against it
+            for slowdown in [1.0, 1.05] {
+                let iters = (1..=100).map(|i| f64::from(i) * 10.0).collect::<Vec<_>>();
+                let times = iters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &iters)| iters * (10.0 + (i % 7) as f64) * slowdown)
+                    .collect();
+                let sample = sample(ActualSamplingMode::Linear, iters, times);
+                let config = criterion.config.clone();
+                analyze(&id, &sample, &config, &criterion, &report_context, None);
+            }
+        };
+
+        let (first, second) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        analyze_in(first.path());
+        analyze_in(second.path());
+
+        for file in ["new/estimates.json", "change/estimates.json"] {
+            let read = |dir: &Path| std::fs::read(dir.join("group/seeded").join(file)).unwrap();
+            assert_eq!(read(first.path()), read(second.path()), "{}", file);
+        }
+        let change =
+            std::fs::read_to_string(first.path().join("group/seeded/change/estimates.json"));
+        assert!(change.unwrap().contains("\"rng_seed\":42"));
+    }
+}
@@ -1,19 +1,52 @@
-use crate::{PlotConfiguration, SamplingMode};
+use crate::{
+    ComparisonReport, ComparisonTest, ConfidenceIntervalMethod, OutlierDetection,
+    PlotConfiguration, RegressionModel, SamplingMode, TrackedStatistic,
+};
 use std::time::Duration;
 
 // TODO: Move the benchmark config stuff to a separate module for easier use.
 
 /// Struct containing all of the configuration options for a benchmark.
+#[derive(Clone)]
 pub struct BenchmarkConfig {
     pub confidence_level: f64,
     pub measurement_time: Duration,
     pub noise_threshold: f64,
+    pub learn_noise_threshold: bool,
     pub nresamples: usize,
     pub sample_size: usize,
     pub significance_level: f64,
     pub warm_up_time: Duration,
+    pub adaptive_warm_up: bool,
     pub sampling_mode: SamplingMode,
     pub quick_mode: bool,
+    pub repetitions: usize,
+    pub target_precision: Option<f64>,
+    pub comparison_test: ComparisonTest,
+    pub comparison_report: ComparisonReport,
+    pub equivalence_margin: Option<f64>,
+    pub target_effect: Option<f64>,
+    pub outlier_detection: OutlierDetection,
+    pub exclude_severe_outliers: bool,
+    pub confidence_interval_method: ConfidenceIntervalMethod,
+    pub regression_model: RegressionModel,
+    pub tracked_statistic: TrackedStatistic,
+    pub additional_statistics: Vec<TrackedStatistic>,
+}
+impl BenchmarkConfig {
+    /// The statistics estimated on request, in addition to the mean and median, without
+    /// duplicates.
+    pub(crate) fn extra_statistics(&self) -> Vec<TrackedStatistic> {
+        let mut statistics = vec![];
+        for &statistic in
+            std::iter::once(&self.tracked_statistic).chain(&self.additional_statistics)
+        {
+            if statistic.is_extra() && !statistics.contains(&statistic) {
+                statistics.push(statistic);
+            }
+        }
+        statistics
+    }
 }
 
 /// Struct representing a partially-complete per-benchmark configuration.
@@ -22,12 +55,25 @@ pub(crate) struct PartialBenchmarkConfig {
     pub(crate) confidence_level: Option<f64>,
     pub(crate) measurement_time: Option<Duration>,
     pub(crate) noise_threshold: Option<f64>,
+    pub(crate) learn_noise_threshold: Option<bool>,
     pub(crate) nresamples: Option<usize>,
     pub(crate) sample_size: Option<usize>,
     pub(crate) significance_level: Option<f64>,
     pub(crate) warm_up_time: Option<Duration>,
+    pub(crate) adaptive_warm_up: Option<bool>,
     pub(crate) sampling_mode: Option<SamplingMode>,
     pub(crate) quick_mode: Option<bool>,
+    pub(crate) target_precision: Option<f64>,
+    pub(crate) comparison_test: Option<ComparisonTest>,
+    pub(crate) comparison_report: Option<ComparisonReport>,
+    pub(crate) equivalence_margin: Option<f64>,
+    pub(crate) target_effect: Option<f64>,
+    pub(crate) outlier_detection: Option<OutlierDetection>,
+    pub(crate) exclude_severe_outliers: Option<bool>,
+    pub(crate) confidence_interval_method: Option<ConfidenceIntervalMethod>,
+    pub(crate) regression_model: Option<RegressionModel>,
+    pub(crate) tracked_statistic: Option<TrackedStatistic>,
+    pub(crate) additional_statistics: Option<Vec<TrackedStatistic>>,
     pub(crate) plot_config: PlotConfiguration,
 }
 
@@ -37,14 +83,40 @@ impl PartialBenchmarkConfig {
             confidence_level: self.confidence_level.unwrap_or(defaults.confidence_level),
             measurement_time: self.measurement_time.unwrap_or(defaults.measurement_time),
             noise_threshold: self.noise_threshold.unwrap_or(defaults.noise_threshold),
+            learn_noise_threshold: self
+                .learn_noise_threshold
+                .unwrap_or(defaults.learn_noise_threshold),
             nresamples: self.nresamples.unwrap_or(defaults.nresamples),
             sample_size: self.sample_size.unwrap_or(defaults.sample_size),
             significance_level: self
                 .significance_level
                 .unwrap_or(defaults.significance_level),
             warm_up_time: self.warm_up_time.unwrap_or(defaults.warm_up_time),
-            sampling_mode: self.sampling_mode.unwrap_or(defaults.sampling_mode),
+            adaptive_warm_up: self.adaptive_warm_up.unwrap_or(defaults.adaptive_warm_up),
+            sampling_mode: self
+                .sampling_mode
+                .clone()
+                .unwrap_or_else(|| defaults.sampling_mode.clone()),
             quick_mode: self.quick_mode.unwrap_or(defaults.quick_mode),
+            repetitions: defaults.repetitions,
+            target_precision: self.target_precision.or(defaults.target_precision),
+            comparison_test: self.comparison_test.unwrap_or(defaults.comparison_test),
+            comparison_report: self.comparison_report.unwrap_or(defaults.comparison_report),
+            equivalence_margin: self.equivalence_margin.or(defaults.equivalence_margin),
+            target_effect: self.target_effect.or(defaults.target_effect),
+            outlier_detection: self.outlier_detection.unwrap_or(defaults.outlier_detection),
+            exclude_severe_outliers: self
+                .exclude_severe_outliers
+                .unwrap_or(defaults.exclude_severe_outliers),
+            confidence_interval_method: self
+                .confidence_interval_method
+                .unwrap_or(defaults.confidence_interval_method),
+            regression_model: self.regression_model.unwrap_or(defaults.regression_model),
+            tracked_statistic: self.tracked_statistic.unwrap_or(defaults.tracked_statistic),
+            additional_statistics: self
+                .additional_statistics
+                .clone()
+                .unwrap_or_else(|| defaults.additional_statistics.clone()),
         }
     }
 }
@@ -1,12 +1,18 @@
 use crate::analysis;
 use crate::benchmark::PartialBenchmarkConfig;
 use crate::connection::OutgoingMessage;
+use crate::fs;
 use crate::measurement::Measurement;
+use crate::reference;
 use crate::report::BenchmarkId as InternalBenchmarkId;
 use crate::report::Report;
 use crate::report::ReportContext;
 use crate::routine::{Function, Routine};
-use crate::{Bencher, Criterion, Mode, PlotConfiguration, SamplingMode, Throughput};
+use crate::{
+    Bencher, ComparisonReport, ComparisonTest, ConfidenceIntervalMethod, Criterion, Mode,
+    OutlierDetection, PlotConfiguration, RegressionModel, SamplingMode, Throughput,
+    TrackedStatistic,
+};
 use std::time::Duration;
 
 /// Structure used to group together a set of related benchmarks, along with custom configuration
@@ -81,6 +87,7 @@ pub struct BenchmarkGroup<'a, M: Measurement> {
     any_matched: bool,
     partial_config: PartialBenchmarkConfig,
     throughput: Option<Throughput>,
+    reference: Option<String>,
 }
 impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
     /// Changes the size of the sample for this benchmark
@@ -112,6 +119,13 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
         self
     }
 
+    /// Enables or disables adaptive warm-up for this benchmark group. See
+    /// [`Criterion::adaptive_warm_up`](crate::Criterion::adaptive_warm_up) for details.
+    pub fn adaptive_warm_up(&mut self, enabled: bool) -> &mut Self {
+        self.partial_config.adaptive_warm_up = Some(enabled);
+        self
+    }
+
     /// Changes the target measurement time for this benchmark group.
     ///
     /// Criterion will attempt to spent approximately this amount of time measuring each
@@ -170,6 +184,13 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
         self
     }
 
+    /// Learns the noise threshold of each benchmark in this group from its history. See
+    /// [`Criterion::learn_noise_threshold`](crate::Criterion::learn_noise_threshold) for details.
+    pub fn learn_noise_threshold(&mut self, learn: bool) -> &mut Self {
+        self.partial_config.learn_noise_threshold = Some(learn);
+        self
+    }
+
     /// Changes the confidence level for benchmarks in this group. The confidence
     /// level is the desired probability that the true runtime lies within the estimated
     /// [confidence interval](https://en.wikipedia.org/wiki/Confidence_interval). The default is
@@ -228,8 +249,163 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
         self
     }
 
+    /// Changes the target precision for this benchmark group. See
+    /// [`Criterion::target_precision`](crate::Criterion::target_precision) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target precision is not positive
+    pub fn target_precision(&mut self, precision: f64) -> &mut Self {
+        assert!(precision > 0.0);
+
+        self.partial_config.target_precision = Some(precision);
+        self
+    }
+
+    /// Changes the linear regression whose slope is reported as the typical time per iteration
+    /// in this benchmark group. See
+    /// [`Criterion::regression_model`](crate::Criterion::regression_model).
+    pub fn regression_model(&mut self, model: RegressionModel) -> &mut Self {
+        self.partial_config.regression_model = Some(model);
+        self
+    }
+
+    /// Changes the method used to compute the confidence intervals of the estimates of this
+    /// benchmark group. See
+    /// [`Criterion::confidence_interval_method`](crate::Criterion::confidence_interval_method).
+    pub fn confidence_interval_method(&mut self, method: ConfidenceIntervalMethod) -> &mut Self {
+        self.partial_config.confidence_interval_method = Some(method);
+        self
+    }
+
+    /// Changes how outliers are identified in the measurements of this benchmark group. See
+    /// [`Criterion::outlier_detection`](crate::Criterion::outlier_detection) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a threshold is not positive, or the severe threshold is smaller than the mild
+    /// one.
+    pub fn outlier_detection(&mut self, detection: OutlierDetection) -> &mut Self {
+        detection.validate();
+
+        self.partial_config.outlier_detection = Some(detection);
+        self
+    }
+
+    /// Excludes severe outliers from the estimates and comparisons of this benchmark group. See
+    /// [`Criterion::exclude_severe_outliers`](crate::Criterion::exclude_severe_outliers) for
+    /// details.
+    pub fn exclude_severe_outliers(&mut self, exclude: bool) -> &mut Self {
+        self.partial_config.exclude_severe_outliers = Some(exclude);
+        self
+    }
+
+    /// Changes the hypothesis test used to compare this benchmark group against the baseline.
+    /// See [`Criterion::comparison_test`](crate::Criterion::comparison_test) for details.
+    pub fn comparison_test(&mut self, test: ComparisonTest) -> &mut Self {
+        self.partial_config.comparison_test = Some(test);
+        self
+    }
+
+    /// Changes how the evidence for a change since the baseline is reported for this benchmark
+    /// group. See [`Criterion::comparison_report`](crate::Criterion::comparison_report) for
+    /// details.
+    pub fn comparison_report(&mut self, report: ComparisonReport) -> &mut Self {
+        self.partial_config.comparison_report = Some(report);
+        self
+    }
+
+    /// Tests the benchmarks of this group for equivalence with the baseline within the given
+    /// relative margin. See
+    /// [`Criterion::equivalence_margin`](crate::Criterion::equivalence_margin) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the margin is not positive
+    pub fn equivalence_margin(&mut self, margin: f64) -> &mut Self {
+        assert!(margin > 0.0);
+
+        self.partial_config.equivalence_margin = Some(margin);
+        self
+    }
+
+    /// Changes the relative change that the power analysis plans for in this benchmark group. See
+    /// [`Criterion::target_effect`](crate::Criterion::target_effect) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the effect is not positive
+    pub fn target_effect(&mut self, effect: f64) -> &mut Self {
+        assert!(effect > 0.0);
+
+        self.partial_config.target_effect = Some(effect);
+        self
+    }
+
+    /// Changes the statistic that decides whether the benchmarks of this group have changed. See
+    /// [`Criterion::tracked_statistic`](crate::Criterion::tracked_statistic) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a percentile is not between 1 and 99.
+    pub fn tracked_statistic(&mut self, statistic: TrackedStatistic) -> &mut Self {
+        statistic.validate();
+        self.partial_config.tracked_statistic = Some(statistic);
+        self
+    }
+
+    /// Changes the statistics estimated in addition to the mean, median and tracked statistic for
+    /// this benchmark group. See
+    /// [`Criterion::additional_statistics`](crate::Criterion::additional_statistics) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a percentile is not between 1 and 99.
+    pub fn additional_statistics(&mut self, statistics: &[TrackedStatistic]) -> &mut Self {
+        statistics.iter().for_each(|s| s.validate());
+        self.partial_config.additional_statistics = Some(statistics.to_vec());
+        self
+    }
+
+    /// Marks one of the functions of this benchmark group as the reference. Once the group is
+    /// finished, every other benchmark is compared against the reference benchmark with the same
+    /// parameter, and reported as a ratio of their mean times (eg. `2.30× faster than std`), with
+    /// a bootstrapped confidence interval on the ratio. The ratios are shown in the group summary
+    /// and repeated at the end of the run.
+    ///
+    /// Benchmarks without a reference benchmark of the same parameter are not compared.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use criterion::{BenchmarkId, Criterion};
+    /// # let mut c = Criterion::default();
+    /// let mut group = c.benchmark_group("sort");
+    /// group.reference("std");
+    /// for size in [100, 1000] {
+    ///     let data: Vec<u64> = (0..size).rev().collect();
+    ///     group.bench_with_input(BenchmarkId::new("std", size), &data, |b, data| {
+    ///         b.iter(|| data.clone().sort())
+    ///     });
+    ///     group.bench_with_input(BenchmarkId::new("unstable", size), &data, |b, data| {
+    ///         b.iter(|| data.clone().sort_unstable())
+    ///     });
+    /// }
+    /// group.finish();
+    /// ```
+    pub fn reference(&mut self, function_name: &str) -> &mut Self {
+        self.reference = Some(function_name.to_owned());
+        self
+    }
+
     /// Set the sampling mode for this benchmark group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a custom schedule contains fewer than two iteration counts, or an iteration
+    /// count of zero.
     pub fn sampling_mode(&mut self, new_mode: SamplingMode) -> &mut Self {
+        new_mode.validate();
         self.partial_config.sampling_mode = Some(new_mode);
         self
     }
@@ -242,6 +418,7 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
             any_matched: false,
             partial_config: PartialBenchmarkConfig::default(),
             throughput: None,
+            reference: None,
         }
     }
 
@@ -280,6 +457,134 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
             plot_config: self.partial_config.plot_config.clone(),
         };
 
+        let (id, do_run) = self.register_id(id);
+        let mut func = Function::new(f);
+
+        match &self.criterion.mode {
+            Mode::Benchmark => {
+                self.announce(&id, do_run);
+                if do_run {
+                    self.criterion.check_environment();
+                    analysis::common(
+                        &id,
+                        &mut func,
+                        &config,
+                        self.criterion,
+                        &report_context,
+                        input,
+                        self.throughput.clone(),
+                    );
+                }
+            }
+            Mode::List(_) => {
+                if do_run {
+                    println!("{}: benchmark", id);
+                }
+            }
+            Mode::Test => {
+                if do_run {
+                    // In test mode, run the benchmark exactly once, then exit.
+                    self.criterion.report.test_start(&id, &report_context);
+                    func.test(&self.criterion.measurement, input);
+                    self.criterion.report.test_pass(&id, &report_context);
+                }
+            }
+            &Mode::Profile(duration) => {
+                if do_run {
+                    func.profile(
+                        &self.criterion.measurement,
+                        &id,
+                        self.criterion,
+                        &report_context,
+                        duration,
+                        input,
+                    );
+                }
+            }
+        }
+
+        self.all_ids.push(id);
+    }
+
+    /// Benchmark several parameterless functions with their measurements interleaved, then
+    /// compare each of them against the first one.
+    ///
+    /// Normally each benchmark is measured to completion before the next one starts, so a slow
+    /// drift in the performance of the machine (eg. thermal throttling or a background process)
+    /// can look like a difference between the functions. Interleaved benchmarks instead take one
+    /// sample of each function in turn, and the comparison uses a paired test on the samples
+    /// taken at the same time. The functions are also analyzed and reported individually as
+    /// usual.
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use criterion::{criterion_group, criterion_main, Criterion};
+    ///
+    /// fn bench(c: &mut Criterion) {
+    ///     let mut group = c.benchmark_group("Sorting");
+    ///     let data: Vec<u64> = (0..1000).rev().collect();
+    ///
+    ///     group
+    ///         .interleaved()
+    ///         .bench_function("sort", |b| b.iter(|| data.clone().sort()))
+    ///         .bench_function("sort_unstable", |b| b.iter(|| data.clone().sort_unstable()))
+    ///         .finish();
+    ///     group.finish();
+    /// }
+    ///
+    /// criterion_group!(benches, bench);
+    /// criterion_main!(benches);
+    /// ```
+    pub fn interleaved<'g>(&'g mut self) -> InterleavedBenchmarks<'g, 'a, M> {
+        InterleavedBenchmarks {
+            group: self,
+            functions: vec![],
+        }
+    }
+
+    /// Collect benchmarks to be run together, in a random order if shuffling is enabled (see
+    /// [`Criterion::shuffle`]), and in the order they were added otherwise.
+    ///
+    /// The benchmarks added directly to the group with [`bench_function`](Self::bench_function)
+    /// and [`bench_with_input`](Self::bench_with_input) are run as soon as they are added, since
+    /// their closures and inputs may borrow from the caller only until the call returns, so they
+    /// can't be reordered. The benchmarks added to the returned set are instead kept until it is
+    /// finished, and their inputs are owned by it.
+    ///
+    /// # Examples:
+    ///
+    /// ```no_run
+    /// use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+    ///
+    /// fn bench(c: &mut Criterion) {
+    ///     let mut group = c.benchmark_group("Sum");
+    ///
+    ///     let mut shuffled = group.shuffled();
+    ///     for size in [10u64, 100, 1000] {
+    ///         shuffled.bench_with_input(BenchmarkId::new("iter", size), size, |b, &size| {
+    ///             b.iter(|| (0..size).sum::<u64>())
+    ///         });
+    ///         shuffled.bench_with_input(BenchmarkId::new("formula", size), size, |b, &size| {
+    ///             b.iter(|| size * (size - 1) / 2)
+    ///         });
+    ///     }
+    ///     shuffled.finish();
+    ///     group.finish();
+    /// }
+    ///
+    /// criterion_group!(benches, bench);
+    /// criterion_main!(benches);
+    /// ```
+    pub fn shuffled<'g>(&'g mut self) -> ShuffledBenchmarks<'g, 'a, M> {
+        ShuffledBenchmarks {
+            group: self,
+            benchmarks: vec![],
+        }
+    }
+
+    // Assigns a unique ID to a new benchmark and returns whether it matches the filter.
+    fn register_id(&mut self, id: BenchmarkId) -> (InternalBenchmarkId, bool) {
         let mut id = InternalBenchmarkId::new(
             self.group_name.clone(),
             id.function_name,
@@ -302,59 +607,85 @@ impl<'a, M: Measurement> BenchmarkGroup<'a, M> {
 
         let do_run = self.criterion.filter_matches(id.id());
         self.any_matched |= do_run;
-        let mut func = Function::new(f);
+        (id, do_run)
+    }
+
+    fn announce(&self, id: &InternalBenchmarkId, do_run: bool) {
+        if let Some(conn) = &self.criterion.connection {
+            if do_run {
+                conn.send(&OutgoingMessage::BeginningBenchmark { id: id.into() })
+                    .unwrap();
+            } else {
+                conn.send(&OutgoingMessage::SkippingBenchmark { id: id.into() })
+                    .unwrap();
+            }
+        }
+    }
+
+    fn run_interleaved(&mut self, functions: Vec<(BenchmarkId, Box<dyn Routine<M, ()> + '_>)>) {
+        let config = self.partial_config.to_complete(&self.criterion.config);
+        let report_context = ReportContext {
+            output_directory: self.criterion.output_directory.clone(),
+            plot_config: self.partial_config.plot_config.clone(),
+        };
+
+        let mut ids = vec![];
+        let mut to_run = vec![];
+        for (id, func) in functions {
+            let (id, do_run) = self.register_id(id);
+            if do_run {
+                to_run.push((id.clone(), func));
+            }
+            if self.criterion.mode.is_benchmark() {
+                self.announce(&id, do_run);
+            }
+            ids.push(id);
+        }
 
         match &self.criterion.mode {
             Mode::Benchmark => {
-                if let Some(conn) = &self.criterion.connection {
-                    if do_run {
-                        conn.send(&OutgoingMessage::BeginningBenchmark { id: (&id).into() })
-                            .unwrap();
-                    } else {
-                        conn.send(&OutgoingMessage::SkippingBenchmark { id: (&id).into() })
-                            .unwrap();
-                    }
-                }
-                if do_run {
-                    analysis::common(
-                        &id,
-                        &mut func,
+                if !to_run.is_empty() {
+                    self.criterion.check_environment();
+                    let mut benchmarks = to_run
+                        .iter_mut()
+                        .map(|(id, func)| (&*id, func.as_mut() as &mut dyn Routine<M, ()>))
+                        .collect::<Vec<_>>();
+                    analysis::interleaved(
+                        &mut benchmarks,
                         &config,
                         self.criterion,
                         &report_context,
-                        input,
                         self.throughput.clone(),
                     );
                 }
             }
             Mode::List(_) => {
-                if do_run {
+                for (id, _) in &to_run {
                     println!("{}: benchmark", id);
                 }
             }
             Mode::Test => {
-                if do_run {
-                    // In test mode, run the benchmark exactly once, then exit.
-                    self.criterion.report.test_start(&id, &report_context);
-                    func.test(&self.criterion.measurement, input);
-                    self.criterion.report.test_pass(&id, &report_context);
+                for (id, func) in &mut to_run {
+                    self.criterion.report.test_start(id, &report_context);
+                    func.test(&self.criterion.measurement, &());
+                    self.criterion.report.test_pass(id, &report_context);
                 }
             }
             &Mode::Profile(duration) => {
-                if do_run {
+                for (id, func) in &mut to_run {
                     func.profile(
                         &self.criterion.measurement,
-                        &id,
+                        id,
                         self.criterion,
                         &report_context,
                         duration,
-                        input,
+                        &(),
                     );
                 }
             }
         }
 
-        self.all_ids.push(id);
+        self.all_ids.extend(ids);
     }
 
     /// Consume the benchmark group and generate the summary reports for the group.
@@ -385,9 +716,35 @@ impl<'a, M: Measurement> Drop for BenchmarkGroup<'a, M> {
                 plot_config: self.partial_config.plot_config.clone(),
             };
 
+            let relative = match &self.reference {
+                Some(reference_name) => {
+                    let config = self.partial_config.to_complete(&self.criterion.config);
+                    let all_ids = self
+                        .all_ids
+                        .iter()
+                        .filter(|id| {
+                            fs::is_dir(
+                                &report_context.output_directory.join(id.as_directory_name()),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    let data = report_context.load_summary_data(&all_ids);
+                    analysis::reseed(self.criterion, analysis::REFERENCE_STREAM);
+                    reference::compare(
+                        reference_name,
+                        &data,
+                        config.nresamples,
+                        config.confidence_level,
+                    )
+                }
+                None => vec![],
+            };
+            reference::record(&relative);
+
             self.criterion.report.summarize(
                 &report_context,
                 &self.all_ids,
+                &relative,
                 self.criterion.measurement.formatter(),
             );
         }
@@ -497,3 +854,102 @@ impl<S: Into<String>> IntoBenchmarkId for S {
         }
     }
 }
+
+/// A set of functions to be benchmarked with their measurements interleaved. Created by
+/// [`BenchmarkGroup::interleaved`].
+///
+/// The functions are benchmarked when this is finished or dropped. The first function is used as
+/// the reference that the others are compared against.
+pub struct InterleavedBenchmarks<'g, 'a, M: Measurement> {
+    group: &'g mut BenchmarkGroup<'a, M>,
+    functions: Vec<(BenchmarkId, Box<dyn Routine<M, ()> + 'g>)>,
+}
+impl<'g, 'a, M: Measurement> InterleavedBenchmarks<'g, 'a, M> {
+    /// Add a parameterless function to the set of interleaved benchmarks.
+    pub fn bench_function<ID: IntoBenchmarkId, F>(mut self, id: ID, mut f: F) -> Self
+    where
+        F: FnMut(&mut Bencher<'_, M>) + 'g,
+    {
+        self.functions.push((
+            id.into_benchmark_id(),
+            Box::new(Function::new(move |b: &mut Bencher<'_, M>, _: &()| f(b))),
+        ));
+        self
+    }
+
+    /// Benchmark the functions and compare them.
+    ///
+    /// It is recommended to call this explicitly, but if you forget it will be called when the
+    /// set is dropped.
+    pub fn finish(self) {
+        ::std::mem::drop(self);
+    }
+}
+impl<'g, 'a, M: Measurement> Drop for InterleavedBenchmarks<'g, 'a, M> {
+    fn drop(&mut self) {
+        let functions = ::std::mem::take(&mut self.functions);
+        self.group.run_interleaved(functions);
+    }
+}
+
+/// A set of benchmarks to be run in a random order when shuffling is enabled. Created by
+/// [`BenchmarkGroup::shuffled`].
+///
+/// The benchmarks are run when this is finished or dropped.
+pub struct ShuffledBenchmarks<'g, 'a, M: Measurement> {
+    group: &'g mut BenchmarkGroup<'a, M>,
+    #[allow(clippy::type_complexity)]
+    benchmarks: Vec<Box<dyn FnOnce(&mut BenchmarkGroup<'a, M>) + 'g>>,
+}
+impl<'g, 'a, M: Measurement> ShuffledBenchmarks<'g, 'a, M> {
+    /// Add a function to the set of shuffled benchmarks.
+    pub fn bench_function<ID: IntoBenchmarkId, F>(&mut self, id: ID, mut f: F) -> &mut Self
+    where
+        F: FnMut(&mut Bencher<'_, M>) + 'g,
+    {
+        let id = id.into_benchmark_id();
+        self.benchmarks
+            .push(Box::new(move |group| group.run_bench(id, &(), |b, _| f(b))));
+        self
+    }
+
+    /// Add a parameterized function to the set of shuffled benchmarks. The input is kept until
+    /// the benchmark is run.
+    pub fn bench_with_input<ID: IntoBenchmarkId, F, I>(
+        &mut self,
+        id: ID,
+        input: I,
+        f: F,
+    ) -> &mut Self
+    where
+        F: FnMut(&mut Bencher<'_, M>, &I) + 'g,
+        I: 'g,
+    {
+        let id = id.into_benchmark_id();
+        self.benchmarks
+            .push(Box::new(move |group| group.run_bench(id, &input, f)));
+        self
+    }
+
+    /// Run the benchmarks.
+    ///
+    /// It is recommended to call this explicitly, but if you forget it will be called when the
+    /// set is dropped.
+    pub fn finish(self) {
+        ::std::mem::drop(self);
+    }
+}
+impl<'g, 'a, M: Measurement> Drop for ShuffledBenchmarks<'g, 'a, M> {
+    fn drop(&mut self) {
+        let mut benchmarks = ::std::mem::take(&mut self.benchmarks);
+        if let Some(seed) = self.group.criterion.shuffle_seed {
+            if self.group.criterion.report.cli_enabled {
+                crate::announce_shuffle_seed(seed);
+            }
+            crate::shuffle_with_seed(seed, &mut benchmarks);
+        }
+        for benchmark in benchmarks {
+            benchmark(self.group);
+        }
+    }
+}
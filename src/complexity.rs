@@ -0,0 +1,258 @@
+//! Asymptotic complexity fitting
+//!
+//! When the benchmarks of a group are parameterized by a number (the input size, typically), the
+//! average time per iteration of each function is fitted against that number with each of a set
+//! of candidate complexity models. The model that explains the most of the variance in the times
+//! is reported as the complexity of the function.
+
+use std::fmt;
+
+use crate::report::BenchmarkId;
+use crate::stats::univariate::Sample;
+
+/// The minimum number of distinct parameter values needed to fit the complexity of a function.
+const MIN_POINTS: usize = 3;
+
+/// A candidate complexity model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Exponential,
+}
+impl Complexity {
+    const ALL: [Complexity; 6] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Exponential,
+    ];
+
+    /// The growth function of this model, evaluated at `n`
+    pub fn basis(self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::Logarithmic => n.log2(),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * n.log2(),
+            Complexity::Quadratic => n * n,
+            Complexity::Exponential => n.exp2(),
+        }
+    }
+
+    /// The growth function, as it is written after the coefficient (eg. `2 ns × n log n`)
+    fn term(self) -> &'static str {
+        match self {
+            Complexity::Constant => "",
+            Complexity::Logarithmic => "log n",
+            Complexity::Linear => "n",
+            Complexity::Linearithmic => "n log n",
+            Complexity::Quadratic => "n²",
+            Complexity::Exponential => "2ⁿ",
+        }
+    }
+}
+impl fmt::Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Complexity::Constant => f.pad("O(1)"),
+            term => f.pad(&format!("O({})", term.term())),
+        }
+    }
+}
+
+/// The best fitting complexity model of a function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ComplexityFit {
+    pub complexity: Complexity,
+    /// The time taken per unit of the growth function
+    pub coefficient: f64,
+    /// The coefficient of determination of the fit. The constant model always scores zero.
+    pub r_squared: f64,
+}
+impl ComplexityFit {
+    /// Fits each candidate model `y = coefficient * basis(x)` by least squares and returns the one
+    /// with the highest coefficient of determination. Models whose growth function isn't finite
+    /// at every `x` (eg. the logarithm of zero), or whose fit isn't finite, are skipped. Returns
+    /// `None` if there are fewer than three distinct values of `x`.
+    pub fn fit(xs: &[f64], ys: &[f64]) -> Option<ComplexityFit> {
+        let mut distinct = xs.to_vec();
+        distinct.sort_by(|a, b| a.total_cmp(b));
+        distinct.dedup();
+        if distinct.len() < MIN_POINTS {
+            return None;
+        }
+
+        let n = ys.len() as f64;
+        let y_bar = ys.iter().sum::<f64>() / n;
+        let ss_tot: f64 = ys.iter().map(|&y| (y - y_bar).powi(2)).sum();
+
+        Complexity::ALL
+            .iter()
+            .filter_map(|&complexity| {
+                let basis: Vec<f64> = xs.iter().map(|&x| complexity.basis(x)).collect();
+                if basis.iter().any(|b| !b.is_finite()) {
+                    return None;
+                }
+
+                let bb: f64 = basis.iter().map(|b| b * b).sum();
+                let by: f64 = basis.iter().zip(ys).map(|(b, y)| b * y).sum();
+                if bb == 0.0 {
+                    return None;
+                }
+                let coefficient = by / bb;
+
+                let ss_res: f64 = basis
+                    .iter()
+                    .zip(ys)
+                    .map(|(b, y)| (y - coefficient * b).powi(2))
+                    .sum();
+                let r_squared = if ss_tot > 0.0 {
+                    1.0 - ss_res / ss_tot
+                } else {
+                    0.0
+                };
+                // The growth function can overflow when it's squared or scaled, even if it's finite
+                if !coefficient.is_finite() || !r_squared.is_finite() {
+                    return None;
+                }
+
+                Some(ComplexityFit {
+                    complexity,
+                    coefficient,
+                    r_squared,
+                })
+            })
+            // The first of equally good models is the simplest one
+            .fold(None, |best: Option<ComplexityFit>, fit| match best {
+                Some(best) if best.r_squared >= fit.r_squared => Some(best),
+                _ => Some(fit),
+            })
+    }
+
+    /// Evaluates the fitted model at `n`
+    pub fn predict(&self, n: f64) -> f64 {
+        self.coefficient * self.complexity.basis(n)
+    }
+
+    /// Samples the fitted model between the smallest and largest of `xs`, for plotting. The
+    /// points are spaced evenly on a logarithmic scale if the values are all positive.
+    pub fn curve(&self, xs: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        const POINTS: usize = 100;
+
+        let min = xs.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = xs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let step = |i: usize| i as f64 / (POINTS - 1) as f64;
+        let curve_xs: Vec<f64> = if min > 0.0 {
+            (0..POINTS)
+                .map(|i| min * (max / min).powf(step(i)))
+                .collect()
+        } else {
+            (0..POINTS).map(|i| min + (max - min) * step(i)).collect()
+        };
+        let curve_ys = curve_xs.iter().map(|&x| self.predict(x)).collect();
+        (curve_xs, curve_ys)
+    }
+
+    /// A short description of the fit for plot legends, eg. `O(n) (R² = 0.9990)`
+    pub fn label(&self) -> String {
+        format!("{} (R² = {:0.4})", self.complexity, self.r_squared)
+    }
+
+    /// Describes the fit, formatting the coefficient with the given function
+    pub fn describe(&self, format_value: impl Fn(f64) -> String) -> String {
+        let coefficient = format_value(self.coefficient);
+        let formula = match self.complexity {
+            Complexity::Constant => coefficient,
+            complexity => format!("{} × {}", coefficient, complexity.term()),
+        };
+        format!(
+            "{} ≈ {} (R² = {:0.4})",
+            self.complexity, formula, self.r_squared
+        )
+    }
+}
+
+/// Fits the complexity of each function of a group, given the average iteration times of each
+/// benchmark. Functions whose benchmarks aren't all parameterized by a number are skipped.
+pub(crate) fn fit_functions<'a>(
+    all_curves: &[&(&'a BenchmarkId, Vec<f64>)],
+) -> Vec<(Option<&'a String>, ComplexityFit)> {
+    let mut function_ids: Vec<Option<&'a String>> = vec![];
+    for &&(id, _) in all_curves {
+        if !function_ids.contains(&id.function_id.as_ref()) {
+            function_ids.push(id.function_id.as_ref());
+        }
+    }
+
+    function_ids
+        .into_iter()
+        .filter_map(|function_id| {
+            let (xs, ys): (Vec<f64>, Vec<f64>) = all_curves
+                .iter()
+                .filter(|&&&(id, _)| id.function_id.as_ref() == function_id)
+                .map(|&&(id, ref times)| Some((id.as_number()?, Sample::new(times).mean())))
+                .collect::<Option<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            ComplexityFit::fit(&xs, &ys).map(|fit| (function_id, fit))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Complexity, ComplexityFit};
+
+    fn fit(complexity: Complexity) -> ComplexityFit {
+        let xs: Vec<f64> = (1..=10).map(|i| f64::from(i) * 4.0).collect();
+        let ys: Vec<f64> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| 3.0 * complexity.basis(x) * (1.0 + 0.01 * (i % 2) as f64))
+            .collect();
+        ComplexityFit::fit(&xs, &ys).unwrap()
+    }
+
+    #[test]
+    fn recovers_model() {
+        for complexity in [
+            Complexity::Logarithmic,
+            Complexity::Linear,
+            Complexity::Linearithmic,
+            Complexity::Quadratic,
+            Complexity::Exponential,
+        ] {
+            let fit = fit(complexity);
+            assert_eq!(complexity, fit.complexity);
+            assert!(fit.r_squared > 0.99);
+        }
+    }
+
+    #[test]
+    fn constant() {
+        let fit = ComplexityFit::fit(&[1.0, 2.0, 4.0, 8.0], &[5.0, 5.0, 5.0, 5.0]).unwrap();
+        assert_eq!(Complexity::Constant, fit.complexity);
+        assert_eq!(5.0, fit.coefficient);
+    }
+
+    #[test]
+    fn skips_overflowing_model() {
+        // 2ⁿ is finite at each size, but its square and its products with the times are not
+        let xs = [1000.0, 1005.0, 1010.0, 1015.0, 1020.0];
+        let ys = xs.map(|x| 3000.0 * x);
+        let fit = ComplexityFit::fit(&xs, &ys).unwrap();
+        assert_eq!(Complexity::Linear, fit.complexity);
+        assert!(fit.coefficient.is_finite());
+    }
+
+    #[test]
+    fn needs_three_sizes() {
+        assert_eq!(None, ComplexityFit::fit(&[1.0, 2.0, 2.0], &[1.0, 2.0, 2.0]));
+    }
+}
@@ -375,11 +375,14 @@ pub enum SamplingMethod {
     Linear,
     Flat,
 }
-impl From<crate::ActualSamplingMode> for SamplingMethod {
-    fn from(other: crate::ActualSamplingMode) -> Self {
+impl From<&crate::ActualSamplingMode> for SamplingMethod {
+    fn from(other: &crate::ActualSamplingMode) -> Self {
         match other {
             crate::ActualSamplingMode::Flat => SamplingMethod::Flat,
-            crate::ActualSamplingMode::Linear => SamplingMethod::Linear,
+            // cargo-criterion only distinguishes between flat sampling and varied iteration counts.
+            crate::ActualSamplingMode::Linear
+            | crate::ActualSamplingMode::Geometric
+            | crate::ActualSamplingMode::Custom(_) => SamplingMethod::Linear,
         }
     }
 }
@@ -0,0 +1,155 @@
+//! Correction for multiple comparisons
+//!
+//! Each benchmark is compared against its baseline independently, so with enough benchmarks some
+//! of them are bound to appear to change by chance alone. When a correction is requested, every
+//! comparison of the run is recorded here, whichever group it belongs to, and the verdicts wait
+//! for the end of the run. The p-values of all of the comparisons are then adjusted together
+//! before deciding whether each one changed.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::estimate::{ChangeEstimates, Estimate};
+use crate::fs;
+use crate::report::BenchmarkId;
+use crate::MultipleComparisonCorrection;
+
+/// A comparison against the baseline, waiting for the end of the run to be corrected.
+pub(crate) struct RecordedComparison {
+    pub id: BenchmarkId,
+    /// The correction requested for the comparison
+    pub method: MultipleComparisonCorrection,
+    /// The p-value of the change in the tracked statistic
+    pub p_value: f64,
+    /// The change in the tracked statistic
+    pub change: Estimate,
+    pub significance_threshold: f64,
+    pub noise_threshold: f64,
+    /// The change estimates, saved again with the adjusted p-value once it is known
+    pub estimates: ChangeEstimates,
+    pub estimates_file: PathBuf,
+}
+
+/// The comparisons corrected together, along with their adjusted p-values.
+pub(crate) struct CorrectedComparisons {
+    pub method: MultipleComparisonCorrection,
+    pub comparisons: Vec<RecordedComparison>,
+    pub adjusted_p_values: Vec<f64>,
+}
+
+static RECORDED: Mutex<Vec<RecordedComparison>> = Mutex::new(Vec::new());
+
+/// Adjusts the p-values of a family of comparisons using the given method.
+pub(crate) fn adjust(method: MultipleComparisonCorrection, p_values: &[f64]) -> Vec<f64> {
+    match method {
+        MultipleComparisonCorrection::None => p_values.to_vec(),
+        MultipleComparisonCorrection::Holm => holm(p_values),
+        MultipleComparisonCorrection::BenjaminiHochberg => benjamini_hochberg(p_values),
+    }
+}
+
+/// Records a comparison, to be corrected at the end of the run.
+pub(crate) fn record(comparison: RecordedComparison) {
+    RECORDED.lock().unwrap().push(comparison);
+}
+
+/// Corrects the comparisons recorded so far, together with the others which requested the same
+/// method, and saves their adjusted p-values with their change estimates.
+pub(crate) fn correct() -> Vec<CorrectedComparisons> {
+    let recorded = std::mem::take(&mut *RECORDED.lock().unwrap());
+    let mut families: BTreeMap<&str, Vec<RecordedComparison>> = BTreeMap::new();
+    for comparison in recorded {
+        families
+            .entry(comparison.method.name())
+            .or_default()
+            .push(comparison);
+    }
+
+    families
+        .into_values()
+        .map(|mut comparisons| {
+            let method = comparisons[0].method;
+            let p_values = comparisons
+                .iter()
+                .map(|comparison| comparison.p_value)
+                .collect::<Vec<_>>();
+            let adjusted_p_values = adjust(method, &p_values);
+            for (comparison, &adjusted) in comparisons.iter_mut().zip(&adjusted_p_values) {
+                if let Some(test) = &mut comparison.estimates.test {
+                    test.adjusted_p_value = Some(adjusted);
+                }
+                log_if_err!(fs::save(&comparison.estimates, &comparison.estimates_file));
+            }
+            CorrectedComparisons {
+                method,
+                comparisons,
+                adjusted_p_values,
+            }
+        })
+        .collect()
+}
+
+// Returns the indices of the p-values, sorted by ascending p-value
+fn ascending(p_values: &[f64]) -> Vec<usize> {
+    let mut order = (0..p_values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| p_values[a].total_cmp(&p_values[b]));
+    order
+}
+
+/// Holm-Bonferroni adjusted p-values, which control the family-wise error rate.
+fn holm(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut adjusted = vec![0.0; m];
+    let mut running_max = 0.0_f64;
+    for (rank, i) in ascending(p_values).into_iter().enumerate() {
+        running_max = running_max.max(((m - rank) as f64 * p_values[i]).min(1.0));
+        adjusted[i] = running_max;
+    }
+    adjusted
+}
+
+/// Benjamini-Hochberg adjusted p-values, which control the false discovery rate.
+fn benjamini_hochberg(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut adjusted = vec![0.0; m];
+    let mut running_min = 1.0_f64;
+    for (rank, i) in ascending(p_values).into_iter().enumerate().rev() {
+        running_min = running_min.min(m as f64 / (rank + 1) as f64 * p_values[i]);
+        adjusted[i] = running_min;
+    }
+    adjusted
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{benjamini_hochberg, holm};
+
+    fn assert_all_relative_eq(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert_relative_eq!(a, e, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn holm_adjustment() {
+        let p = [0.01, 0.04, 0.03, 0.005];
+        assert_all_relative_eq(&holm(&p), &[0.03, 0.06, 0.06, 0.02]);
+    }
+
+    #[test]
+    fn benjamini_hochberg_adjustment() {
+        let p = [0.01, 0.04, 0.03, 0.005];
+        assert_all_relative_eq(&benjamini_hochberg(&p), &[0.02, 0.04, 0.04, 0.02]);
+    }
+
+    #[test]
+    fn adjusted_p_values_are_capped() {
+        let p = [0.5, 0.9, 0.7];
+        assert!(holm(&p).iter().all(|&p| p <= 1.0));
+        assert!(benjamini_hochberg(&p).iter().all(|&p| p <= 1.0));
+    }
+}
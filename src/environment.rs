@@ -0,0 +1,259 @@
+//! Preflight checks for common sources of system noise.
+//!
+//! Before any benchmarks are run, Criterion.rs inspects the system for settings which are known to
+//! make measurements less reliable - power-saving CPU governors, turbo boost, other load on the
+//! machine, running on battery and benchmarking a debug build. The findings are reported as
+//! warnings and saved with each sample. Turbo boost is only ever a warning: it's enabled on most
+//! machines, and refusing to run because of it would make `--strict-env` unusable.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One-minute load average per CPU above which the system is considered busy.
+const LOAD_PER_CPU_THRESHOLD: f64 = 1.0;
+
+/// The results of inspecting the system before benchmarking. Any piece of information which could
+/// not be determined (eg. because the platform doesn't expose it) is left as `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub(crate) struct EnvironmentReport {
+    /// The distinct CPU frequency scaling governors in use, if any.
+    #[serde(default)]
+    pub(crate) cpu_governors: Vec<String>,
+    /// Whether turbo boost is enabled.
+    #[serde(default)]
+    pub(crate) turbo_enabled: Option<bool>,
+    /// The one-minute load average.
+    #[serde(default)]
+    pub(crate) load_average: Option<f64>,
+    /// The number of CPUs available to the process, which the load average is relative to.
+    #[serde(default)]
+    pub(crate) cpu_count: Option<usize>,
+    /// Whether the system is running on battery power.
+    #[serde(default)]
+    pub(crate) on_battery: Option<bool>,
+    /// Whether Criterion.rs was compiled with debug assertions enabled.
+    #[serde(default)]
+    pub(crate) debug_assertions: bool,
+}
+impl EnvironmentReport {
+    /// Inspects the current system.
+    pub(crate) fn inspect() -> EnvironmentReport {
+        EnvironmentReport {
+            cpu_governors: cpu_governors(),
+            turbo_enabled: turbo_enabled(),
+            load_average: load_average(),
+            cpu_count: std::thread::available_parallelism().ok().map(|n| n.get()),
+            on_battery: on_battery(),
+            debug_assertions: cfg!(debug_assertions),
+        }
+    }
+
+    /// Returns a human-readable description of each problem found with the environment.
+    pub(crate) fn warnings(&self) -> Vec<String> {
+        self.findings()
+            .into_iter()
+            .map(|(warning, _)| warning)
+            .collect()
+    }
+
+    /// Returns a human-readable description of each problem found with the environment that is
+    /// serious enough for `--strict-env` to refuse to run.
+    pub(crate) fn refusals(&self) -> Vec<String> {
+        self.findings()
+            .into_iter()
+            .filter(|&(_, refuse)| refuse)
+            .map(|(warning, _)| warning)
+            .collect()
+    }
+
+    /// Returns true if any problems that `--strict-env` refuses to run with were found.
+    pub(crate) fn is_noisy(&self) -> bool {
+        !self.refusals().is_empty()
+    }
+
+    // Each problem found, along with whether strict checking refuses to run because of it.
+    fn findings(&self) -> Vec<(String, bool)> {
+        let mut findings = vec![];
+
+        let slow_governors = self
+            .cpu_governors
+            .iter()
+            .filter(|governor| governor.as_str() != "performance")
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !slow_governors.is_empty() {
+            findings.push((
+                format!(
+                    "CPU frequency governor is set to '{}'; consider using 'performance' instead.",
+                    slow_governors.join("', '")
+                ),
+                true,
+            ));
+        }
+        if self.turbo_enabled == Some(true) {
+            findings.push((
+                "Turbo boost is enabled; CPU frequency may vary with temperature and load."
+                    .to_owned(),
+                false,
+            ));
+        }
+        if let Some(load) = self.load_average {
+            let cpus = self.cpu_count.unwrap_or(1).max(1);
+            if load / cpus as f64 > LOAD_PER_CPU_THRESHOLD {
+                findings.push((
+                    format!(
+                        "System load average is {:.2} across {} CPUs; other processes may interfere with the measurements.",
+                        load, cpus
+                    ),
+                    true,
+                ));
+            }
+        }
+        if self.on_battery == Some(true) {
+            findings.push(("System is running on battery power.".to_owned(), true));
+        }
+        if self.debug_assertions {
+            findings.push((
+                "Benchmarks were compiled with debug assertions enabled; results will not reflect \
+                optimized performance."
+                    .to_owned(),
+                true,
+            ));
+        }
+
+        findings
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_owned())
+}
+
+fn cpu_governors() -> Vec<String> {
+    let mut governors = vec![];
+    let entries = match std::fs::read_dir("/sys/devices/system/cpu") {
+        Ok(entries) => entries,
+        Err(_) => return governors,
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let is_cpu = name
+            .strip_prefix("cpu")
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if !is_cpu {
+            continue;
+        }
+        if let Some(governor) = read_trimmed(&entry.path().join("cpufreq/scaling_governor")) {
+            if !governors.contains(&governor) {
+                governors.push(governor);
+            }
+        }
+    }
+    governors.sort_unstable();
+    governors
+}
+
+fn turbo_enabled() -> Option<bool> {
+    // intel_pstate exposes the inverse setting; other drivers use the generic boost flag.
+    if let Some(no_turbo) = read_trimmed(Path::new("/sys/devices/system/cpu/intel_pstate/no_turbo"))
+    {
+        return Some(no_turbo == "0");
+    }
+    read_trimmed(Path::new("/sys/devices/system/cpu/cpufreq/boost")).map(|boost| boost == "1")
+}
+
+fn load_average() -> Option<f64> {
+    read_trimmed(Path::new("/proc/loadavg"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn on_battery() -> Option<bool> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    let mut found_supply = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match read_trimmed(&path.join("type")).as_deref() {
+            Some("Mains") => {
+                found_supply = true;
+                if read_trimmed(&path.join("online")).as_deref() == Some("1") {
+                    return Some(false);
+                }
+            }
+            Some("Battery") => {
+                found_supply = true;
+                if read_trimmed(&path.join("status")).as_deref() == Some("Discharging") {
+                    return Some(true);
+                }
+            }
+            _ => {}
+        }
+    }
+    if found_supply {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_quiet_environment_has_no_warnings() {
+        let report = EnvironmentReport {
+            cpu_governors: vec!["performance".to_owned()],
+            turbo_enabled: Some(false),
+            load_average: Some(0.1),
+            cpu_count: Some(1),
+            on_battery: Some(false),
+            debug_assertions: false,
+        };
+        assert!(report.warnings().is_empty());
+        assert!(!report.is_noisy());
+    }
+
+    #[test]
+    fn test_noisy_environment_is_reported() {
+        let report = EnvironmentReport {
+            cpu_governors: vec!["performance".to_owned(), "powersave".to_owned()],
+            turbo_enabled: Some(true),
+            load_average: Some(3.5),
+            cpu_count: Some(2),
+            on_battery: Some(true),
+            debug_assertions: true,
+        };
+        let warnings = report.warnings();
+        assert_eq!(5, warnings.len());
+        assert!(warnings[0].contains("'powersave'"));
+        assert_eq!(4, report.refusals().len());
+        assert!(report.is_noisy());
+    }
+
+    #[test]
+    fn test_load_is_relative_to_cpu_count() {
+        let report = EnvironmentReport {
+            load_average: Some(3.5),
+            cpu_count: Some(8),
+            ..EnvironmentReport::default()
+        };
+        assert!(report.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_turbo_is_only_a_warning() {
+        let report = EnvironmentReport {
+            turbo_enabled: Some(true),
+            ..EnvironmentReport::default()
+        };
+        assert_eq!(1, report.warnings().len());
+        assert!(report.refusals().is_empty());
+        assert!(!report.is_noisy());
+    }
+}
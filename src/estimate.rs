@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 use crate::stats::Distribution;
+use crate::{
+    ComparisonTest, ConfidenceIntervalMethod, MultipleComparisonCorrection, RegressionModel,
+    TrackedStatistic,
+};
 
 #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Deserialize, Serialize, Debug)]
 pub enum Statistic {
@@ -11,6 +16,20 @@ pub enum Statistic {
     Slope,
     StdDev,
     Typical,
+    Min,
+    TrimmedMean,
+    Percentile(u8),
+}
+impl From<TrackedStatistic> for Statistic {
+    fn from(statistic: TrackedStatistic) -> Statistic {
+        match statistic {
+            TrackedStatistic::Mean => Statistic::Mean,
+            TrackedStatistic::Median => Statistic::Median,
+            TrackedStatistic::Min => Statistic::Min,
+            TrackedStatistic::TrimmedMean => Statistic::TrimmedMean,
+            TrackedStatistic::Percentile(p) => Statistic::Percentile(p),
+        }
+    }
 }
 
 impl fmt::Display for Statistic {
@@ -22,6 +41,9 @@ impl fmt::Display for Statistic {
             Statistic::Slope => f.pad("slope"),
             Statistic::StdDev => f.pad("SD"),
             Statistic::Typical => f.pad("typical"),
+            Statistic::Min => f.pad("min"),
+            Statistic::TrimmedMean => f.pad("trimmed mean"),
+            Statistic::Percentile(p) => f.pad(&format!("p{}", p)),
         }
     }
 }
@@ -31,6 +53,9 @@ pub struct ConfidenceInterval {
     pub confidence_level: f64,
     pub lower_bound: f64,
     pub upper_bound: f64,
+    /// The method used to compute the interval from the bootstrap distribution
+    #[serde(default)]
+    pub method: ConfidenceIntervalMethod,
 }
 
 #[derive(Clone, PartialEq, Deserialize, Serialize, Debug)]
@@ -43,56 +68,99 @@ pub struct Estimate {
     pub standard_error: f64,
 }
 
+/// Builds the estimate of a statistic from its point estimate and bootstrap distribution. If an
+/// `acceleration` is given, the confidence interval is bias-corrected and accelerated, otherwise
+/// it is a percentile interval.
+pub fn build_estimate(
+    point_estimate: f64,
+    distribution: &Distribution<f64>,
+    acceleration: Option<f64>,
+    cl: f64,
+) -> Estimate {
+    let ((lb, ub), method) = match acceleration {
+        Some(acceleration) => (
+            distribution.bca_confidence_interval(point_estimate, acceleration, cl),
+            ConfidenceIntervalMethod::Bca,
+        ),
+        None => (
+            distribution.confidence_interval(cl),
+            ConfidenceIntervalMethod::Percentile,
+        ),
+    };
+
+    Estimate {
+        confidence_interval: ConfidenceInterval {
+            confidence_level: cl,
+            lower_bound: lb,
+            upper_bound: ub,
+            method,
+        },
+        point_estimate,
+        standard_error: distribution.std_dev(None),
+    }
+}
+
 pub fn build_estimates(
     distributions: &Distributions,
     points: &PointEstimates,
+    accelerations: Option<&PointEstimates>,
     cl: f64,
 ) -> Estimates {
-    let to_estimate = |point_estimate, distribution: &Distribution<f64>| {
-        let (lb, ub) = distribution.confidence_interval(cl);
-
-        Estimate {
-            confidence_interval: ConfidenceInterval {
-                confidence_level: cl,
-                lower_bound: lb,
-                upper_bound: ub,
-            },
+    let to_estimate = |point_estimate, distribution, acceleration: fn(&PointEstimates) -> f64| {
+        build_estimate(
             point_estimate,
-            standard_error: distribution.std_dev(None),
-        }
+            distribution,
+            accelerations.map(acceleration),
+            cl,
+        )
     };
 
     Estimates {
-        mean: to_estimate(points.mean, &distributions.mean),
-        median: to_estimate(points.median, &distributions.median),
-        median_abs_dev: to_estimate(points.median_abs_dev, &distributions.median_abs_dev),
+        mean: to_estimate(points.mean, &distributions.mean, |a| a.mean),
+        median: to_estimate(points.median, &distributions.median, |a| a.median),
+        median_abs_dev: to_estimate(points.median_abs_dev, &distributions.median_abs_dev, |a| {
+            a.median_abs_dev
+        }),
         slope: None,
-        std_dev: to_estimate(points.std_dev, &distributions.std_dev),
+        std_dev: to_estimate(points.std_dev, &distributions.std_dev, |a| a.std_dev),
+        excluded_outliers: None,
+        intercept: None,
+        slope_with_intercept: None,
+        regression_model: RegressionModel::ThroughOrigin,
+        rng_seed: None,
+        min: None,
+        trimmed_mean: None,
+        percentiles: BTreeMap::new(),
     }
 }
 
 pub fn build_change_estimates(
     distributions: &ChangeDistributions,
     points: &ChangePointEstimates,
+    accelerations: Option<&ChangePointEstimates>,
     cl: f64,
 ) -> ChangeEstimates {
-    let to_estimate = |point_estimate, distribution: &Distribution<f64>| {
-        let (lb, ub) = distribution.confidence_interval(cl);
-
-        Estimate {
-            confidence_interval: ConfidenceInterval {
-                confidence_level: cl,
-                lower_bound: lb,
-                upper_bound: ub,
-            },
-            point_estimate,
-            standard_error: distribution.std_dev(None),
-        }
-    };
-
     ChangeEstimates {
-        mean: to_estimate(points.mean, &distributions.mean),
-        median: to_estimate(points.median, &distributions.median),
+        mean: build_estimate(
+            points.mean,
+            &distributions.mean,
+            accelerations.map(|a| a.mean),
+            cl,
+        ),
+        median: build_estimate(
+            points.median,
+            &distributions.median,
+            accelerations.map(|a| a.median),
+            cl,
+        ),
+        effect_sizes: None,
+        probabilities: None,
+        equivalence: None,
+        min: None,
+        trimmed_mean: None,
+        percentiles: BTreeMap::new(),
+        test: None,
+        rng_seed: None,
     }
 }
 
@@ -110,10 +178,39 @@ pub struct Estimates {
     pub median_abs_dev: Estimate,
     pub slope: Option<Estimate>,
     pub std_dev: Estimate,
+    /// The number of severe outliers excluded from the sample before estimating, if any were
+    /// excluded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excluded_outliers: Option<usize>,
+    /// The fixed overhead of each sample, from the regression with an intercept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intercept: Option<Estimate>,
+    /// The time per iteration, from the regression with an intercept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slope_with_intercept: Option<Estimate>,
+    /// The regression whose slope is the typical estimate
+    #[serde(default)]
+    pub regression_model: RegressionModel,
+    /// The seed of the random number generator used to bootstrap the estimates, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng_seed: Option<u64>,
+    /// The minimum time per iteration, if it was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Estimate>,
+    /// The trimmed mean time per iteration, if it was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trimmed_mean: Option<Estimate>,
+    /// The requested percentiles of the time per iteration, by percentile
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub percentiles: BTreeMap<u8, Estimate>,
 }
 impl Estimates {
     pub fn typical(&self) -> &Estimate {
-        self.slope.as_ref().unwrap_or(&self.mean)
+        match self.regression_model {
+            RegressionModel::ThroughOrigin => self.slope.as_ref(),
+            RegressionModel::WithIntercept => self.slope_with_intercept.as_ref(),
+        }
+        .unwrap_or(&self.mean)
     }
     pub fn get(&self, stat: Statistic) -> Option<&Estimate> {
         match stat {
@@ -123,6 +220,38 @@ impl Estimates {
             Statistic::Slope => self.slope.as_ref(),
             Statistic::StdDev => Some(&self.std_dev),
             Statistic::Typical => Some(self.typical()),
+            Statistic::Min => self.min.as_ref(),
+            Statistic::TrimmedMean => self.trimmed_mean.as_ref(),
+            Statistic::Percentile(p) => self.percentiles.get(&p),
+        }
+    }
+    /// The estimates of the statistics estimated on request, in a fixed order
+    pub fn extra_statistics(&self) -> Vec<(TrackedStatistic, &Estimate)> {
+        let min = self.min.as_ref().map(|e| (TrackedStatistic::Min, e));
+        let trimmed_mean = self
+            .trimmed_mean
+            .as_ref()
+            .map(|e| (TrackedStatistic::TrimmedMean, e));
+        let percentiles = self
+            .percentiles
+            .iter()
+            .map(|(&p, e)| (TrackedStatistic::Percentile(p), e));
+        min.into_iter()
+            .chain(trimmed_mean)
+            .chain(percentiles)
+            .collect()
+    }
+    /// Records the estimate of a statistic estimated on request
+    pub fn set_extra(&mut self, stat: TrackedStatistic, estimate: Estimate) {
+        match stat {
+            TrackedStatistic::Min => self.min = Some(estimate),
+            TrackedStatistic::TrimmedMean => self.trimmed_mean = Some(estimate),
+            TrackedStatistic::Percentile(p) => {
+                self.percentiles.insert(p, estimate);
+            }
+            TrackedStatistic::Mean | TrackedStatistic::Median => {
+                panic!("The {} is always estimated", stat)
+            }
         }
     }
 }
@@ -133,10 +262,17 @@ pub struct Distributions {
     pub median_abs_dev: Distribution<f64>,
     pub slope: Option<Distribution<f64>>,
     pub std_dev: Distribution<f64>,
+    pub intercept: Option<Distribution<f64>>,
+    pub slope_with_intercept: Option<Distribution<f64>>,
+    pub regression_model: RegressionModel,
 }
 impl Distributions {
     pub fn typical(&self) -> &Distribution<f64> {
-        self.slope.as_ref().unwrap_or(&self.mean)
+        match self.regression_model {
+            RegressionModel::ThroughOrigin => self.slope.as_ref(),
+            RegressionModel::WithIntercept => self.slope_with_intercept.as_ref(),
+        }
+        .unwrap_or(&self.mean)
     }
     pub fn get(&self, stat: Statistic) -> Option<&Distribution<f64>> {
         match stat {
@@ -146,6 +282,7 @@ impl Distributions {
             Statistic::Slope => self.slope.as_ref(),
             Statistic::StdDev => Some(&self.std_dev),
             Statistic::Typical => Some(self.typical()),
+            _ => None,
         }
     }
 }
@@ -155,17 +292,160 @@ pub struct ChangePointEstimates {
     pub median: f64,
 }
 
+/// Effect sizes of the change, comparing the new measurements against the baseline. Each of
+/// them is positive when the new measurements tend to be larger.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EffectSizes {
+    /// Cohen's d: the difference of the means in units of the pooled standard deviation, which
+    /// includes the variance between runs if the measurements were taken in several runs
+    pub cohens_d: f64,
+    /// The Hodges-Lehmann estimate of the shift between the new and the baseline measurements,
+    /// in the units of the measurement
+    pub hodges_lehmann: f64,
+    /// The probability that a new measurement is larger than a baseline measurement
+    pub probability_of_superiority: f64,
+}
+
+/// The probabilities of a change larger than the noise threshold, read from the bootstrap
+/// distribution of the relative change in the tracked statistic.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ChangeProbabilities {
+    /// The noise threshold the change is measured against
+    pub noise_threshold: f64,
+    /// The probability that the new measurements are slower by more than the noise threshold
+    pub regression: f64,
+    /// The probability that the new measurements are faster by more than the noise threshold
+    pub improvement: f64,
+}
+impl ChangeProbabilities {
+    pub fn new(distribution: &Distribution<f64>, noise_threshold: f64) -> ChangeProbabilities {
+        let n = distribution.len() as f64;
+        let fraction = |beyond: &dyn Fn(f64) -> bool| {
+            distribution
+                .iter()
+                .filter(|&&change| beyond(change))
+                .count() as f64
+                / n
+        };
+
+        ChangeProbabilities {
+            noise_threshold,
+            regression: fraction(&|change| change > noise_threshold),
+            improvement: fraction(&|change| change < -noise_threshold),
+        }
+    }
+}
+
+/// The result of an equivalence test (two one-sided tests, or TOST) of the change against a
+/// margin, from the bootstrap distribution of the relative change in the tracked statistic.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Equivalence {
+    /// The relative margin within which the change is considered negligible
+    pub margin: f64,
+    /// The larger of the p-values of the two one-sided tests
+    pub p_value: f64,
+    /// Whether both one-sided tests are significant, ie. the change is within the margin
+    pub equivalent: bool,
+}
+impl Equivalence {
+    pub fn new(
+        distribution: &Distribution<f64>,
+        margin: f64,
+        significance_level: f64,
+    ) -> Equivalence {
+        let n = distribution.len() as f64;
+        // Each one-sided p-value is the fraction of the bootstrap distribution on the wrong side
+        // of its margin
+        let below = distribution
+            .iter()
+            .filter(|&&change| change <= -margin)
+            .count() as f64
+            / n;
+        let above = distribution
+            .iter()
+            .filter(|&&change| change >= margin)
+            .count() as f64
+            / n;
+        let p_value = below.max(above);
+
+        Equivalence {
+            margin,
+            p_value,
+            equivalent: p_value < significance_level,
+        }
+    }
+}
+
+/// The result of the hypothesis test used to decide whether a benchmark has changed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HypothesisTest {
+    /// The statistic whose change was tested
+    pub statistic: TrackedStatistic,
+    /// The test which was run on the change in the mean. Any other statistic is tested with the
+    /// bootstrap distribution of its change instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<ComparisonTest>,
+    pub p_value: f64,
+    /// The method used to correct the p-value for the other comparisons of the group, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correction: Option<MultipleComparisonCorrection>,
+    /// The p-value adjusted for the other comparisons of the group, which decides the verdict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adjusted_p_value: Option<f64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChangeEstimates {
     pub mean: Estimate,
     pub median: Estimate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect_sizes: Option<EffectSizes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub probabilities: Option<ChangeProbabilities>,
+    /// The equivalence test against the baseline, if a margin was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equivalence: Option<Equivalence>,
+    /// The relative change in the minimum, if it was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Estimate>,
+    /// The relative change in the trimmed mean, if it was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trimmed_mean: Option<Estimate>,
+    /// The relative change in the requested percentiles, by percentile
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub percentiles: BTreeMap<u8, Estimate>,
+    /// The hypothesis test of the change against the baseline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test: Option<HypothesisTest>,
+    /// The seed of the random number generator used to bootstrap the change, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rng_seed: Option<u64>,
 }
 impl ChangeEstimates {
     pub fn get(&self, stat: Statistic) -> &Estimate {
+        self.try_get(stat).expect("Unexpected statistic")
+    }
+    pub fn try_get(&self, stat: Statistic) -> Option<&Estimate> {
         match stat {
-            Statistic::Mean => &self.mean,
-            Statistic::Median => &self.median,
-            _ => panic!("Unexpected statistic"),
+            Statistic::Mean => Some(&self.mean),
+            Statistic::Median => Some(&self.median),
+            Statistic::Min => self.min.as_ref(),
+            Statistic::TrimmedMean => self.trimmed_mean.as_ref(),
+            Statistic::Percentile(p) => self.percentiles.get(&p),
+            _ => None,
+        }
+    }
+    /// Records the change in a statistic estimated on request
+    pub fn set_extra(&mut self, stat: TrackedStatistic, estimate: Estimate) {
+        match stat {
+            TrackedStatistic::Min => self.min = Some(estimate),
+            TrackedStatistic::TrimmedMean => self.trimmed_mean = Some(estimate),
+            TrackedStatistic::Percentile(p) => {
+                self.percentiles.insert(p, estimate);
+            }
+            TrackedStatistic::Mean | TrackedStatistic::Median => {
+                panic!("The change in the {} is always estimated", stat)
+            }
         }
     }
 }
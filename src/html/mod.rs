@@ -1,12 +1,16 @@
-use crate::report::{make_filename_safe, BenchmarkId, MeasurementData, Report, ReportContext};
-use crate::stats::bivariate::regression::Slope;
+use crate::report::{
+    change_evidence, format_shift, make_filename_safe, BenchmarkId, MeasurementData, Report,
+    ReportContext,
+};
+use crate::stats::bivariate::regression::{Line, Slope};
 
 use crate::estimate::Estimate;
 use crate::format;
 use crate::fs;
 use crate::measurement::ValueFormatter;
 use crate::plot::{PlotContext, PlotData, Plotter};
-use crate::SavedSample;
+use crate::reference::RelativePerformance;
+use crate::{RegressionModel, TrackedStatistic};
 use criterion_plot::Size;
 use serde::Serialize;
 use std::cell::RefCell;
@@ -38,12 +42,16 @@ struct Context {
     thumbnail_height: usize,
 
     slope: Option<ConfidenceInterval>,
+    slope_with_intercept: Option<ConfidenceInterval>,
+    intercept: Option<ConfidenceInterval>,
     r2: ConfidenceInterval,
     mean: ConfidenceInterval,
     std_dev: ConfidenceInterval,
     median: ConfidenceInterval,
     mad: ConfidenceInterval,
     throughput: Option<ConfidenceInterval>,
+    extra_statistics: Vec<NamedInterval>,
+    power: Power,
 
     additional_plots: Vec<Plot>,
 
@@ -75,6 +83,14 @@ impl IndividualBenchmark {
     }
 }
 
+#[derive(Serialize)]
+struct RelativeBenchmark {
+    name: String,
+    path: String,
+    comparison: String,
+    interval: String,
+}
+
 #[derive(Serialize)]
 struct SummaryContext {
     group_id: String,
@@ -86,6 +102,7 @@ struct SummaryContext {
     line_chart: Option<String>,
 
     benchmarks: Vec<IndividualBenchmark>,
+    relative: Vec<RelativeBenchmark>,
 }
 
 #[derive(Serialize)]
@@ -95,6 +112,21 @@ struct ConfidenceInterval {
     point: String,
 }
 
+#[derive(Serialize)]
+struct Power {
+    power: String,
+    minimum_detectable_effect: String,
+    target_effect: String,
+    required_sample_size: String,
+    required_measurement_time: String,
+}
+
+#[derive(Serialize)]
+struct NamedInterval {
+    name: String,
+    interval: ConfidenceInterval,
+}
+
 #[derive(Serialize)]
 struct Plot {
     name: String,
@@ -111,16 +143,23 @@ impl Plot {
 
 #[derive(Serialize)]
 struct Comparison {
-    p_value: String,
-    inequality: String,
-    significance_level: String,
+    change_name: String,
+    evidence: String,
     explanation: String,
 
     change: ConfidenceInterval,
     thrpt_change: Option<ConfidenceInterval>,
+    effect_sizes: Option<EffectSizes>,
     additional_plots: Vec<Plot>,
 }
 
+#[derive(Serialize)]
+struct EffectSizes {
+    cohens_d: String,
+    shift: String,
+    probability_of_superiority: String,
+}
+
 fn if_exists(output_directory: &Path, path: &Path) -> Option<String> {
     let report_path = path.join("report/index.html");
     if PathBuf::from(output_directory).join(&report_path).is_file() {
@@ -319,6 +358,19 @@ impl Report for Html {
 
         let data = measurements.data;
 
+        // The goodness of fit of the regression line whose slope is the typical estimate
+        let r_squared = |slope: f64| match (
+            measurements.absolute_estimates.regression_model,
+            measurements.absolute_estimates.intercept.as_ref(),
+        ) {
+            (RegressionModel::WithIntercept, Some(intercept)) => Line {
+                intercept: intercept.point_estimate,
+                slope,
+            }
+            .r_squared(&data),
+            _ => Slope(slope).r_squared(&data),
+        };
+
         elapsed! {
             "Generating plots",
             self.generate_plots(id, report_context, formatter, measurements)
@@ -334,6 +386,7 @@ impl Report for Html {
         if measurements.absolute_estimates.slope.is_some() {
             additional_plots.push(Plot::new("Slope", "slope.svg"));
         }
+        additional_plots.push(Plot::new("Iteration Order", "iteration_order.svg"));
 
         let throughput = measurements
             .throughput
@@ -361,30 +414,69 @@ impl Report for Html {
                 .slope
                 .as_ref()
                 .map(time_interval),
+            slope_with_intercept: measurements
+                .absolute_estimates
+                .slope_with_intercept
+                .as_ref()
+                .map(time_interval),
+            intercept: measurements
+                .absolute_estimates
+                .intercept
+                .as_ref()
+                .map(time_interval),
             mean: time_interval(&measurements.absolute_estimates.mean),
             median: time_interval(&measurements.absolute_estimates.median),
             mad: time_interval(&measurements.absolute_estimates.median_abs_dev),
             std_dev: time_interval(&measurements.absolute_estimates.std_dev),
             throughput,
+            extra_statistics: measurements
+                .absolute_estimates
+                .extra_statistics()
+                .into_iter()
+                .map(|(statistic, estimate)| NamedInterval {
+                    name: statistic_title(statistic),
+                    interval: time_interval(estimate),
+                })
+                .collect(),
 
             r2: ConfidenceInterval {
                 lower: format!(
                     "{:0.7}",
-                    Slope(typical_estimate.confidence_interval.lower_bound).r_squared(&data)
+                    r_squared(typical_estimate.confidence_interval.lower_bound)
                 ),
                 upper: format!(
                     "{:0.7}",
-                    Slope(typical_estimate.confidence_interval.upper_bound).r_squared(&data)
-                ),
-                point: format!(
-                    "{:0.7}",
-                    Slope(typical_estimate.point_estimate).r_squared(&data)
+                    r_squared(typical_estimate.confidence_interval.upper_bound)
                 ),
+                point: format!("{:0.7}", r_squared(typical_estimate.point_estimate)),
             },
 
             additional_plots,
 
-            comparison: self.comparison(measurements),
+            power: Power {
+                power: format!("{:.0}%", measurements.power.power * 1e2),
+                minimum_detectable_effect: format!(
+                    "±{:.2}%",
+                    measurements.power.minimum_detectable_effect * 1e2
+                ),
+                target_effect: format!("±{:.2}%", measurements.power.target_effect * 1e2),
+                required_sample_size: match measurements.power.required {
+                    Some((sample_size, _)) if measurements.power.runs > 1 => {
+                        format!("{} per run", sample_size)
+                    }
+                    Some((sample_size, _)) => sample_size.to_string(),
+                    None => "More runs needed".to_owned(),
+                },
+                required_measurement_time: match measurements.power.required {
+                    Some((_, time)) if measurements.power.runs > 1 => {
+                        format!("{} per run", format::time(time.as_nanos() as f64))
+                    }
+                    Some((_, time)) => format::time(time.as_nanos() as f64),
+                    None => "More runs needed".to_owned(),
+                },
+            },
+
+            comparison: self.comparison(measurements, formatter),
         };
 
         let mut report_path = report_context.output_directory.clone();
@@ -404,6 +496,7 @@ impl Report for Html {
         &self,
         context: &ReportContext,
         all_ids: &[BenchmarkId],
+        relative: &[RelativePerformance],
         formatter: &dyn ValueFormatter,
     ) {
         let all_ids = all_ids
@@ -419,7 +512,7 @@ impl Report for Html {
 
         let group_id = all_ids[0].group_id.clone();
 
-        let data = self.load_summary_data(&context.output_directory, &all_ids);
+        let data = context.load_summary_data(&all_ids);
 
         let mut function_ids = BTreeSet::new();
         let mut value_strs = Vec::with_capacity(all_ids.len());
@@ -465,6 +558,7 @@ impl Report for Html {
                 self.generate_summary(
                     &subgroup_id,
                     &samples_with_function,
+                    relative,
                     context,
                     formatter,
                     false,
@@ -483,7 +577,14 @@ impl Report for Html {
                 let subgroup_id =
                     BenchmarkId::new(group_id.clone(), None, Some(value_str.clone()), None);
 
-                self.generate_summary(&subgroup_id, &samples_with_value, context, formatter, false);
+                self.generate_summary(
+                    &subgroup_id,
+                    &samples_with_value,
+                    relative,
+                    context,
+                    formatter,
+                    false,
+                );
             }
         }
 
@@ -511,6 +612,7 @@ impl Report for Html {
         self.generate_summary(
             &BenchmarkId::new(group_id, None, None, None),
             &all_data,
+            relative,
             context,
             formatter,
             true,
@@ -555,16 +657,24 @@ impl Report for Html {
     }
 }
 impl Html {
-    fn comparison(&self, measurements: &MeasurementData<'_>) -> Option<Comparison> {
+    fn comparison(
+        &self,
+        measurements: &MeasurementData<'_>,
+        formatter: &dyn ValueFormatter,
+    ) -> Option<Comparison> {
         if let Some(ref comp) = measurements.comparison {
-            let different_mean = comp.p_value < comp.significance_threshold;
-            let mean_est = &comp.relative_estimates.mean;
+            let different = comp.is_significant();
+            let change_est = comp.verdict_estimate();
             let explanation_str: String;
 
-            if !different_mean {
+            if comp.correction.is_some() {
+                explanation_str =
+                    "Decided at the end of the run, after correction for multiple comparisons."
+                        .to_owned();
+            } else if !different {
                 explanation_str = "No change in performance detected.".to_owned();
             } else {
-                let comparison = compare_to_threshold(mean_est, comp.noise_threshold);
+                let comparison = compare_to_threshold(change_est, comp.noise_threshold);
                 match comparison {
                     ComparisonResult::Improved => {
                         explanation_str = "Performance has improved.".to_owned();
@@ -578,38 +688,57 @@ impl Html {
                 }
             }
 
+            let mut additional_plots = vec![
+                Plot::new("Change in mean", "change/mean.svg"),
+                Plot::new("Change in median", "change/median.svg"),
+            ];
+            if comp.t_test.is_some() {
+                additional_plots.push(Plot::new("T-Test", "change/t-test.svg"));
+            }
+
             let comp = Comparison {
-                p_value: format!("{:.2}", comp.p_value),
-                inequality: (if different_mean { "<" } else { ">" }).to_owned(),
-                significance_level: format!("{:.2}", comp.significance_threshold),
+                change_name: match comp.statistic {
+                    TrackedStatistic::Mean => "time".to_owned(),
+                    statistic => statistic.to_string(),
+                },
+                evidence: change_evidence(comp),
                 explanation: explanation_str,
 
                 change: ConfidenceInterval {
-                    point: format::change(mean_est.point_estimate, true),
-                    lower: format::change(mean_est.confidence_interval.lower_bound, true),
-                    upper: format::change(mean_est.confidence_interval.upper_bound, true),
+                    point: format::change(change_est.point_estimate, true),
+                    lower: format::change(change_est.confidence_interval.lower_bound, true),
+                    upper: format::change(change_est.confidence_interval.upper_bound, true),
                 },
 
                 thrpt_change: measurements.throughput.as_ref().map(|_| {
                     let to_thrpt_estimate = |ratio: f64| 1.0 / (1.0 + ratio) - 1.0;
                     ConfidenceInterval {
-                        point: format::change(to_thrpt_estimate(mean_est.point_estimate), true),
+                        point: format::change(to_thrpt_estimate(change_est.point_estimate), true),
                         lower: format::change(
-                            to_thrpt_estimate(mean_est.confidence_interval.lower_bound),
+                            to_thrpt_estimate(change_est.confidence_interval.lower_bound),
                             true,
                         ),
                         upper: format::change(
-                            to_thrpt_estimate(mean_est.confidence_interval.upper_bound),
+                            to_thrpt_estimate(change_est.confidence_interval.upper_bound),
                             true,
                         ),
                     }
                 }),
 
-                additional_plots: vec![
-                    Plot::new("Change in mean", "change/mean.svg"),
-                    Plot::new("Change in median", "change/median.svg"),
-                    Plot::new("T-Test", "change/t-test.svg"),
-                ],
+                effect_sizes: comp
+                    .relative_estimates
+                    .effect_sizes
+                    .as_ref()
+                    .map(|effect_sizes| EffectSizes {
+                        cohens_d: format!("{:+.3}", effect_sizes.cohens_d),
+                        shift: format_shift(formatter, effect_sizes.hodges_lehmann),
+                        probability_of_superiority: format!(
+                            "{:.3}",
+                            effect_sizes.probability_of_superiority
+                        ),
+                    }),
+
+                additional_plots,
             };
             Some(comp)
         } else {
@@ -655,6 +784,9 @@ impl Html {
                 .iteration_times(plot_ctx_small, plot_data);
         }
 
+        self.plotter
+            .borrow_mut()
+            .iteration_order(plot_ctx, plot_data);
         self.plotter
             .borrow_mut()
             .abs_distributions(plot_ctx, plot_data);
@@ -695,7 +827,9 @@ impl Html {
                     .borrow_mut()
                     .iteration_times(plot_ctx_small, comp_data);
             }
-            self.plotter.borrow_mut().t_test(plot_ctx, comp_data);
+            if comp.t_test.is_some() {
+                self.plotter.borrow_mut().t_test(plot_ctx, comp_data);
+            }
             self.plotter
                 .borrow_mut()
                 .rel_distributions(plot_ctx, comp_data);
@@ -704,33 +838,11 @@ impl Html {
         self.plotter.borrow_mut().wait();
     }
 
-    fn load_summary_data<'a>(
-        &self,
-        output_directory: &Path,
-        all_ids: &[&'a BenchmarkId],
-    ) -> Vec<(&'a BenchmarkId, Vec<f64>)> {
-        all_ids
-            .iter()
-            .filter_map(|id| {
-                let entry = output_directory.join(id.as_directory_name()).join("new");
-
-                let SavedSample { iters, times, .. } =
-                    try_else_return!(fs::load(&entry.join("sample.json")), || None);
-                let avg_times = iters
-                    .into_iter()
-                    .zip(times)
-                    .map(|(iters, time)| time / iters)
-                    .collect::<Vec<_>>();
-
-                Some((*id, avg_times))
-            })
-            .collect::<Vec<_>>()
-    }
-
     fn generate_summary(
         &self,
         id: &BenchmarkId,
         data: &[&(&BenchmarkId, Vec<f64>)],
+        relative: &[RelativePerformance],
         report_context: &ReportContext,
         formatter: &dyn ValueFormatter,
         full_summary: bool,
@@ -776,6 +888,20 @@ impl Html {
                 IndividualBenchmark::from_id(&report_context.output_directory, path_prefix, id)
             })
             .collect();
+        // Only the benchmarks shown on this page, along with their reference
+        let relative = relative
+            .iter()
+            .filter(|relative| {
+                data.iter()
+                    .any(|&&(id, _)| id == &relative.id || id == &relative.reference)
+            })
+            .map(|relative| RelativeBenchmark {
+                name: relative.id.as_title().to_owned(),
+                path: format!("{}/{}", path_prefix, relative.id.as_directory_name()),
+                comparison: relative.comparison(),
+                interval: relative.interval(),
+            })
+            .collect();
 
         let context = SummaryContext {
             group_id: id.as_title().to_owned(),
@@ -787,6 +913,7 @@ impl Html {
             line_chart: line_path.map(|p| p.to_string_lossy().into_owned()),
 
             benchmarks,
+            relative,
         };
 
         let mut report_path = report_context.output_directory.clone();
@@ -803,6 +930,15 @@ impl Html {
     }
 }
 
+// The name of a statistic as it is shown in the tables, eg. "Trimmed mean" or "p99"
+fn statistic_title(statistic: TrackedStatistic) -> String {
+    let name = statistic.to_string();
+    match statistic {
+        TrackedStatistic::Percentile(_) => name,
+        _ => name[..1].to_uppercase() + &name[1..],
+    }
+}
+
 enum ComparisonResult {
     Improved,
     Regressed,
@@ -1,13 +1,54 @@
-use crate::stats::univariate::kde::kernel::Gaussian;
+use crate::stats::univariate::kde::kernel::{Epanechnikov, Gaussian, Kernel, Triangular, Uniform};
 use crate::stats::univariate::kde::{Bandwidth, Kde};
 use crate::stats::univariate::Sample;
+use crate::{KdeBandwidth, KdeKernel};
+
+// Number of points at which the density is evaluated when looking for modes
+const MODE_POINTS: usize = 500;
+
+/// Returns the locations of the modes of the estimated density of the sample, using the given
+/// kernel and bandwidth so that they match the PDF plot
+pub fn modes(sample: &Sample<f64>, kernel: KdeKernel, bandwidth: KdeBandwidth) -> Vec<f64> {
+    let bandwidth = to_bandwidth(bandwidth);
+
+    match kernel {
+        KdeKernel::Gaussian => Kde::new(sample, Gaussian, bandwidth).modes(MODE_POINTS),
+        KdeKernel::Epanechnikov => Kde::new(sample, Epanechnikov, bandwidth).modes(MODE_POINTS),
+        KdeKernel::Triangular => Kde::new(sample, Triangular, bandwidth).modes(MODE_POINTS),
+        KdeKernel::Uniform => Kde::new(sample, Uniform, bandwidth).modes(MODE_POINTS),
+    }
+}
+
+fn to_bandwidth(bandwidth: KdeBandwidth) -> Bandwidth {
+    match bandwidth {
+        KdeBandwidth::Silverman => Bandwidth::Silverman,
+        KdeBandwidth::Scott => Bandwidth::Scott,
+        KdeBandwidth::SheatherJones => Bandwidth::SheatherJones,
+    }
+}
 
 pub fn sweep(
     sample: &Sample<f64>,
     npoints: usize,
     range: Option<(f64, f64)>,
 ) -> (Box<[f64]>, Box<[f64]>) {
-    let (xs, ys, _) = sweep_and_estimate(sample, npoints, range, sample[0]);
+    sweep_with(
+        sample,
+        npoints,
+        range,
+        KdeKernel::Gaussian,
+        KdeBandwidth::Silverman,
+    )
+}
+
+pub fn sweep_with(
+    sample: &Sample<f64>,
+    npoints: usize,
+    range: Option<(f64, f64)>,
+    kernel: KdeKernel,
+    bandwidth: KdeBandwidth,
+) -> (Box<[f64]>, Box<[f64]>) {
+    let (xs, ys, _) = sweep_and_estimate(sample, npoints, range, sample[0], kernel, bandwidth);
     (xs, ys)
 }
 
@@ -16,11 +57,47 @@ pub fn sweep_and_estimate(
     npoints: usize,
     range: Option<(f64, f64)>,
     point_to_estimate: f64,
+    kernel: KdeKernel,
+    bandwidth: KdeBandwidth,
 ) -> (Box<[f64]>, Box<[f64]>, f64) {
-    let x_min = sample.min();
-    let x_max = sample.max();
+    let bandwidth = to_bandwidth(bandwidth);
+
+    match kernel {
+        KdeKernel::Gaussian => sweep_kde(
+            Kde::new(sample, Gaussian, bandwidth),
+            npoints,
+            range,
+            point_to_estimate,
+        ),
+        KdeKernel::Epanechnikov => sweep_kde(
+            Kde::new(sample, Epanechnikov, bandwidth),
+            npoints,
+            range,
+            point_to_estimate,
+        ),
+        KdeKernel::Triangular => sweep_kde(
+            Kde::new(sample, Triangular, bandwidth),
+            npoints,
+            range,
+            point_to_estimate,
+        ),
+        KdeKernel::Uniform => sweep_kde(
+            Kde::new(sample, Uniform, bandwidth),
+            npoints,
+            range,
+            point_to_estimate,
+        ),
+    }
+}
 
-    let kde = Kde::new(sample, Gaussian, Bandwidth::Silverman);
+fn sweep_kde<K: Kernel<f64>>(
+    kde: Kde<'_, f64, K>,
+    npoints: usize,
+    range: Option<(f64, f64)>,
+    point_to_estimate: f64,
+) -> (Box<[f64]>, Box<[f64]>, f64) {
+    let x_min = kde.sample().min();
+    let x_max = kde.sample().max();
     let h = kde.bandwidth();
 
     let (start, end) = match range {
@@ -39,9 +39,12 @@ mod benchmark;
 mod benchmark_group;
 pub mod async_executor;
 mod bencher;
+mod complexity;
 mod connection;
+mod correction;
 #[cfg(feature = "csv_output")]
 mod csv_report;
+mod environment;
 mod error;
 mod estimate;
 mod format;
@@ -50,20 +53,25 @@ mod html;
 mod kde;
 mod macros;
 pub mod measurement;
+mod noise;
 mod plot;
 pub mod profiler;
+mod reference;
 mod report;
 mod routine;
+mod scheduling;
 mod stats;
 
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::env;
+use std::fmt;
 use std::io::{stdout, IsTerminal};
 use std::net::TcpStream;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, Once};
 use std::time::Duration;
 
 use criterion_plot::{Version, VersionError};
@@ -72,6 +80,7 @@ use std::sync::OnceLock;
 use crate::benchmark::BenchmarkConfig;
 use crate::connection::Connection;
 use crate::connection::OutgoingMessage;
+use crate::environment::EnvironmentReport;
 use crate::html::Html;
 use crate::measurement::{Measurement, WallTime};
 #[cfg(feature = "plotters")]
@@ -79,11 +88,17 @@ use crate::plot::PlottersBackend;
 use crate::plot::{Gnuplot, Plotter};
 use crate::profiler::{ExternalProfiler, Profiler};
 use crate::report::{BencherReport, CliReport, CliVerbosity, Report, ReportContext, Reports};
+use crate::scheduling::{AppliedScheduling, SchedulingConfig};
+use crate::stats::univariate::outliers::mad;
+use crate::stats::univariate::outliers::tukey::{self, LabeledSample};
+use crate::stats::univariate::Sample;
 
 #[cfg(feature = "async")]
 pub use crate::bencher::AsyncBencher;
 pub use crate::bencher::Bencher;
-pub use crate::benchmark_group::{BenchmarkGroup, BenchmarkId};
+pub use crate::benchmark_group::{
+    BenchmarkGroup, BenchmarkId, InterleavedBenchmarks, ShuffledBenchmarks,
+};
 
 fn gnuplot_version() -> &'static Result<Version, VersionError> {
     static GNUPLOT_VERSION: OnceLock<Result<Version, VersionError>> = OnceLock::new();
@@ -144,6 +159,45 @@ fn default_output_directory() -> &'static PathBuf {
     })
 }
 
+fn system_environment() -> &'static EnvironmentReport {
+    static SYSTEM_ENVIRONMENT: OnceLock<EnvironmentReport> = OnceLock::new();
+
+    SYSTEM_ENVIRONMENT.get_or_init(EnvironmentReport::inspect)
+}
+
+/// The seed used to shuffle the benchmarks when no seed is given explicitly. This is chosen once
+/// per process so that every group is shuffled with the same seed.
+fn random_shuffle_seed() -> u64 {
+    static RANDOM_SHUFFLE_SEED: OnceLock<u64> = OnceLock::new();
+
+    *RANDOM_SHUFFLE_SEED.get_or_init(|| crate::stats::rand_util::new_rng().rand_u64())
+}
+
+/// Shuffles `items` using the given seed. Each call in a process uses a different stream derived
+/// from the seed, so that groups of the same size are not all shuffled into the same order.
+fn shuffle_with_seed<T>(seed: u64, items: &mut [T]) {
+    static SHUFFLE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+    let stream = SHUFFLE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let mut rng = oorandom::Rand64::new(((seed as u128) << 64) | stream as u128);
+    for i in (1..items.len()).rev() {
+        let j = rng.rand_range(0..(i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+/// Prints the shuffle seed (once per process) so that the order can be reproduced.
+fn announce_shuffle_seed(seed: u64) {
+    static ANNOUNCED: Once = Once::new();
+
+    ANNOUNCED.call_once(|| {
+        println!(
+            "Shuffling benchmark order with seed {} (use --shuffle-seed {} to reproduce)",
+            seed, seed
+        );
+    });
+}
+
 fn debug_enabled() -> bool {
     static DEBUG_ENABLED: OnceLock<bool> = OnceLock::new();
 
@@ -359,6 +413,11 @@ pub struct Criterion<M: Measurement = WallTime> {
     profiler: Box<RefCell<dyn Profiler>>,
     connection: Option<MutexGuard<'static, Connection>>,
     mode: Mode,
+    scheduling: SchedulingConfig,
+    strict_environment: bool,
+    shuffle_seed: Option<u64>,
+    rng_seed: Option<u64>,
+    correction: MultipleComparisonCorrection,
 }
 
 /// Returns the Cargo target directory, possibly calling `cargo metadata` to
@@ -408,12 +467,26 @@ impl Default for Criterion {
                 confidence_level: 0.95,
                 measurement_time: Duration::from_secs(5),
                 noise_threshold: 0.01,
+                learn_noise_threshold: false,
                 nresamples: 100_000,
                 sample_size: 100,
                 significance_level: 0.05,
                 warm_up_time: Duration::from_secs(3),
+                adaptive_warm_up: false,
                 sampling_mode: SamplingMode::Auto,
                 quick_mode: false,
+                repetitions: 1,
+                target_precision: None,
+                comparison_test: ComparisonTest::TTest,
+                comparison_report: ComparisonReport::PValue,
+                equivalence_margin: None,
+                target_effect: None,
+                outlier_detection: OutlierDetection::default(),
+                exclude_severe_outliers: false,
+                confidence_interval_method: ConfidenceIntervalMethod::Percentile,
+                regression_model: RegressionModel::ThroughOrigin,
+                tracked_statistic: TrackedStatistic::Mean,
+                additional_statistics: vec![],
             },
             filter: BenchmarkFilter::AcceptAll,
             report: reports,
@@ -429,6 +502,11 @@ impl Default for Criterion {
                 .as_ref()
                 .map(|mtx| mtx.lock().unwrap()),
             mode: Mode::Benchmark,
+            scheduling: SchedulingConfig::default(),
+            strict_environment: false,
+            shuffle_seed: None,
+            rng_seed: None,
+            correction: MultipleComparisonCorrection::None,
         };
 
         if criterion.connection.is_some() {
@@ -461,6 +539,11 @@ impl<M: Measurement> Criterion<M> {
             profiler: self.profiler,
             connection: self.connection,
             mode: self.mode,
+            scheduling: self.scheduling,
+            strict_environment: self.strict_environment,
+            shuffle_seed: self.shuffle_seed,
+            rng_seed: self.rng_seed,
+            correction: self.correction,
         }
     }
 
@@ -526,6 +609,21 @@ impl<M: Measurement> Criterion<M> {
         self
     }
 
+    #[must_use]
+    /// Enables or disables adaptive warm-up for benchmarks run with this runner.
+    ///
+    /// Rather than always warming up for the full warm-up time, adaptive warm-up watches the time
+    /// per iteration of successive batches and ends the warm-up once it has settled: a line fitted
+    /// to the second half of the batches must change by less than 1% along them, allowing for the
+    /// uncertainty of its slope. Noisy benchmarks need more batches to show this. The warm-up
+    /// time becomes an upper limit. This saves time for benchmarks which reach a steady
+    /// state quickly, while still allowing slow effects such as lazy initialization, page faults
+    /// or allocator growth to settle, up to the limit.
+    pub fn adaptive_warm_up(mut self, enabled: bool) -> Criterion<M> {
+        self.config.adaptive_warm_up = enabled;
+        self
+    }
+
     #[must_use]
     /// Changes the default measurement time for benchmarks run with this runner.
     ///
@@ -586,6 +684,22 @@ impl<M: Measurement> Criterion<M> {
         self
     }
 
+    #[must_use]
+    /// Learns the noise threshold of each benchmark from its history, instead of using the same
+    /// [noise threshold](Criterion::noise_threshold) for all of them. Disabled by default.
+    ///
+    /// Each comparison against the previous run, while saving a baseline, records the relative
+    /// change in the [tracked statistic](Criterion::tracked_statistic) in `noise_history.json`.
+    /// Comparisons against a fixed baseline (`--baseline`) aren't recorded. The history keeps the
+    /// 30 most recent changes. Once at least 5 of them have been recorded, the noise threshold is
+    /// set to three times their robust standard deviation (but at least 0.1%), so very stable
+    /// benchmarks can detect smaller changes and noisy ones stop reporting spurious regressions.
+    /// Until then, the configured noise threshold is used. The history is recorded whether or not this is enabled.
+    pub fn learn_noise_threshold(mut self, learn: bool) -> Criterion<M> {
+        self.config.learn_noise_threshold = learn;
+        self
+    }
+
     #[must_use]
     /// Changes the default confidence level for benchmarks run with this runner. The confidence
     /// level is the desired probability that the true runtime lies within the estimated
@@ -633,6 +747,303 @@ impl<M: Measurement> Criterion<M> {
         self
     }
 
+    #[must_use]
+    /// Keep sampling each benchmark until the confidence interval of the typical estimate is
+    /// narrower than the given relative width, rather than for a fixed amount of time. For
+    /// example, a target precision of 0.01 keeps sampling until the estimate is known to within
+    /// ±1%.
+    ///
+    /// Samples are collected in rounds, each taking about a tenth of the measurement time, and a
+    /// quick bootstrap analysis (with fewer resamples than the final one) is run after each round.
+    /// The measurement time becomes an upper limit on the time spent sampling, not counting the
+    /// checks between rounds: sampling stops once it has been reached even if the target precision
+    /// has not. Stable benchmarks therefore finish quickly, while noisy ones get as much time as
+    /// is allowed.
+    ///
+    /// This is ignored in quick mode and for interleaved benchmarks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target precision is not positive
+    pub fn target_precision(mut self, precision: f64) -> Criterion<M> {
+        assert!(precision > 0.0);
+
+        self.config.target_precision = Some(precision);
+        self
+    }
+
+    #[must_use]
+    /// Changes the hypothesis test used to decide whether each benchmark has changed since the
+    /// baseline. Defaults to [`ComparisonTest::TTest`].
+    ///
+    /// The nonparametric tests make no assumption about the shape of the distribution of the
+    /// measurements, so they are better suited to benchmarks with heavy tails or several modes.
+    /// The chosen test and its p-value are reported alongside the change.
+    ///
+    /// When both the sample and the baseline were taken in several [runs](Self::repetitions),
+    /// the nonparametric tests compare the means of the runs instead of the measurements, since
+    /// the measurements of one run aren't independent of each other. With only a few runs they
+    /// can't reach a small p-value: the permutation test with two runs of each can't do better
+    /// than p = 0.33.
+    pub fn comparison_test(mut self, test: ComparisonTest) -> Criterion<M> {
+        self.config.comparison_test = test;
+        self
+    }
+
+    #[must_use]
+    /// Changes how the evidence for a change since the baseline is reported. Defaults to
+    /// [`ComparisonReport::PValue`].
+    ///
+    /// The probability of a change is read from the bootstrap distribution of the relative change
+    /// in the [tracked statistic](Criterion::tracked_statistic), treating it as the posterior
+    /// distribution of the change. It is the fraction of that distribution beyond the noise
+    /// threshold in the direction of the observed change, eg. "93% likely a regression of more
+    /// than 2%". This is often easier to act on than a p-value and a confidence interval.
+    pub fn comparison_report(mut self, report: ComparisonReport) -> Criterion<M> {
+        self.config.comparison_report = report;
+        self
+    }
+
+    #[must_use]
+    /// Tests each benchmark for equivalence with the baseline, within the given relative margin
+    /// (eg. 0.02 for ±2%). Disabled by default.
+    ///
+    /// Failing to detect a change is not evidence that there is none; the benchmark may simply be
+    /// too noisy to detect it. Two one-sided tests (TOST) check whether the change in the
+    /// [tracked statistic](Criterion::tracked_statistic) is both above the lower margin and below
+    /// the upper margin, each at the significance level. If both are significant, the benchmark
+    /// is reported as equivalent to the baseline, which positively shows that eg. a refactor is
+    /// performance-neutral. The margin should be at least the noise threshold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the margin is not positive
+    pub fn equivalence_margin(mut self, margin: f64) -> Criterion<M> {
+        assert!(margin > 0.0);
+
+        self.config.equivalence_margin = Some(margin);
+        self
+    }
+
+    #[must_use]
+    /// Changes the relative change (eg. 0.01 for 1%) that the power analysis plans for. Defaults
+    /// to the noise threshold.
+    ///
+    /// After each benchmark is analyzed, the sample size and measurement time needed to detect a
+    /// change of this size in the mean with 80% power are estimated from the variance of the
+    /// measurements, along with the smallest change that the current configuration can detect.
+    /// Both are shown in the verbose output and the HTML report. A benchmark that can only detect
+    /// changes much larger than the ones that matter can't show that nothing has changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the effect is not positive
+    pub fn target_effect(mut self, effect: f64) -> Criterion<M> {
+        assert!(effect > 0.0);
+
+        self.config.target_effect = Some(effect);
+        self
+    }
+
+    #[must_use]
+    /// Changes the statistic whose change since the baseline decides whether each benchmark has
+    /// improved or regressed. Defaults to [`TrackedStatistic::Mean`].
+    ///
+    /// The mean is the most precise estimate of the time per iteration, but tail latencies or the
+    /// best case can matter more, and they can change without the mean changing much. The
+    /// comparison test only applies to the mean: the change in any other statistic is tested
+    /// with its bootstrap distribution, whose two-sided p-value is twice the fraction of the
+    /// distribution on the far side of zero. That p-value is corrected for multiple comparisons
+    /// and compared with the significance level like that of the comparison test.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a percentile is not between 1 and 99.
+    pub fn tracked_statistic(mut self, statistic: TrackedStatistic) -> Criterion<M> {
+        statistic.validate();
+        self.config.tracked_statistic = statistic;
+        self
+    }
+
+    #[must_use]
+    /// Changes the statistics estimated and reported in addition to the mean, median and tracked
+    /// statistic, eg. `[TrackedStatistic::Min, TrackedStatistic::Percentile(99)]`. Each of them is
+    /// bootstrapped separately, which adds to the analysis time. Defaults to none.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a percentile is not between 1 and 99.
+    pub fn additional_statistics(mut self, statistics: &[TrackedStatistic]) -> Criterion<M> {
+        statistics.iter().for_each(|s| s.validate());
+        self.config.additional_statistics = statistics.to_vec();
+        self
+    }
+
+    #[must_use]
+    /// Changes the linear regression whose slope is reported as the typical time per iteration.
+    /// Defaults to a line through the origin. This has no effect with flat sampling, where the
+    /// mean is the typical statistic.
+    ///
+    /// A line with an intercept separates the fixed overhead of each sample from the time per
+    /// iteration; the intercept is reported either way.
+    pub fn regression_model(mut self, model: RegressionModel) -> Criterion<M> {
+        self.config.regression_model = model;
+        self
+    }
+
+    #[must_use]
+    /// Changes the method used to compute the confidence intervals of the estimates. Defaults to
+    /// percentile intervals. The method used for each interval is recorded in the estimates
+    /// saved in the output directory.
+    pub fn confidence_interval_method(mut self, method: ConfidenceIntervalMethod) -> Criterion<M> {
+        self.config.confidence_interval_method = method;
+        self
+    }
+
+    #[must_use]
+    /// Changes how outliers are identified in the measurements of benchmarks run with this
+    /// runner. Defaults to Tukey's fences with multipliers of 1.5 and 3.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a threshold is not positive, or the severe threshold is smaller than the mild
+    /// one.
+    pub fn outlier_detection(mut self, detection: OutlierDetection) -> Criterion<M> {
+        detection.validate();
+
+        self.config.outlier_detection = detection;
+        self
+    }
+
+    #[must_use]
+    /// Excludes severe outliers from the estimates and comparisons of benchmarks run with this
+    /// runner. Disabled by default.
+    ///
+    /// Severe outliers are usually caused by something outside of the benchmark, such as the
+    /// thread being preempted, so excluding them can give more stable estimates. However, if the
+    /// benchmark genuinely takes much longer some of the time, excluding them hides that. The
+    /// number of excluded measurements is printed and recorded in `estimates.json`; the saved
+    /// sample and the plots still include them.
+    pub fn exclude_severe_outliers(mut self, exclude: bool) -> Criterion<M> {
+        self.config.exclude_severe_outliers = exclude;
+        self
+    }
+
+    #[must_use]
+    /// Corrects the comparisons against the baseline for the number of benchmarks compared in the
+    /// run. Defaults to [`MultipleComparisonCorrection::None`].
+    ///
+    /// When many benchmarks are compared at once, some of them will appear to have changed by
+    /// chance alone; at a significance level of 0.05, about one in twenty. With a correction, each
+    /// benchmark is reported with its change and unadjusted p-value, but whether it changed is
+    /// only decided at the end of the run, once the p-values of all of the comparisons made in the
+    /// run, in every group, have been adjusted together. The final summary then gives the verdict
+    /// for each of the changes which were significant before correction, with both their raw and
+    /// adjusted p-values, and the adjusted p-values are saved in `change/estimates.json`.
+    pub fn multiple_comparison_correction(
+        mut self,
+        correction: MultipleComparisonCorrection,
+    ) -> Criterion<M> {
+        self.correction = correction;
+        self
+    }
+
+    #[must_use]
+    /// Changes the number of independent runs of each benchmark. Each run repeats the whole
+    /// warm-up and sampling cycle, with a short pause in between, and the runs are then pooled
+    /// into one estimate. The default is 1.
+    ///
+    /// A single run underestimates the variation between runs (eg. due to memory layout or the
+    /// state of the machine), so a difference that is significant within one run may disappear on
+    /// the next. With several runs, the confidence intervals are computed using a hierarchical
+    /// bootstrap, which accounts for the variance between runs as well as within them. The
+    /// estimates of each individual run are saved in `runs.json`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if n is zero
+    pub fn repetitions(mut self, n: usize) -> Criterion<M> {
+        assert!(n > 0);
+
+        self.config.repetitions = n;
+        self
+    }
+
+    #[must_use]
+    /// Pins the thread which runs the benchmarks to the given CPU core while measurements are
+    /// being taken. This prevents the operating system from migrating the benchmark between
+    /// cores, which can add noise to the measurements. The original affinity is restored once
+    /// each benchmark has been sampled, so the analysis can still use every core.
+    ///
+    /// This is currently only supported on Linux. On other platforms, a warning is printed once
+    /// and the setting is ignored. The core that was actually used is recorded in the saved
+    /// sample.
+    pub fn pin_to_cpu(mut self, core: usize) -> Criterion<M> {
+        self.scheduling.cpu = Some(core);
+        self
+    }
+
+    #[must_use]
+    /// Changes the scheduling priority (nice value) of the thread which runs the benchmarks while
+    /// measurements are being taken. Lower values mean higher priority; setting a negative value
+    /// usually requires elevated privileges.
+    ///
+    /// This is currently only supported on Linux. On other platforms, or if the priority cannot be
+    /// changed, a warning is printed once and the setting is ignored. The nice value that was
+    /// actually used is recorded in the saved sample.
+    ///
+    /// The previous priority is restored once each benchmark has been sampled, but unprivileged
+    /// users can't lower the nice value again, so after a positive nice value the restore fails
+    /// silently and the thread keeps the lower priority for the rest of the run.
+    pub fn nice(mut self, nice: i32) -> Criterion<M> {
+        self.scheduling.nice = Some(nice);
+        self
+    }
+
+    #[must_use]
+    /// Refuse to run benchmarks if the preflight checks find that the system is noisy.
+    ///
+    /// Before benchmarking, Criterion.rs checks for settings which are known to make measurements
+    /// unreliable, such as a power-saving CPU frequency governor, turbo boost, a high load average
+    /// (relative to the number of CPUs), running on battery power, or a build with debug
+    /// assertions enabled. By default these are only reported as warnings. With strict checking
+    /// enabled, the process exits with an error instead, except for turbo boost which is still
+    /// only a warning. Analysis-only runs with `--load-baseline` are never refused.
+    pub fn strict_environment(mut self, strict: bool) -> Criterion<M> {
+        self.strict_environment = strict;
+        self
+    }
+
+    #[must_use]
+    /// Run the targets of each `criterion_group!` in a random order, rather than in the order they
+    /// were declared. This can reveal order effects, where a benchmark is affected by the ones run
+    /// before it (eg. through allocator fragmentation or cache pollution).
+    ///
+    /// If no seed is given, a random one is chosen. Either way, the seed is printed and recorded
+    /// in the saved sample so that the order can be reproduced. The order of the groups in
+    /// `criterion_main!` is only shuffled when using the `--shuffle` or `--shuffle-seed`
+    /// command-line options. The benchmarks added directly to a `BenchmarkGroup` run as soon as
+    /// they are added, so they can't be reordered; to shuffle them, add them to the set returned
+    /// by [`BenchmarkGroup::shuffled`] instead.
+    pub fn shuffle(mut self, seed: Option<u64>) -> Criterion<M> {
+        self.shuffle_seed = Some(seed.unwrap_or_else(random_shuffle_seed));
+        self
+    }
+
+    #[must_use]
+    /// Seeds the random number generator used by the bootstrap and the permutation test, so that
+    /// analyzing the same measurements again gives exactly the same estimates and verdicts. This
+    /// is useful when reanalyzing saved samples with `--load-baseline`. By default, the generator
+    /// is seeded from the clock and the confidence intervals vary slightly between analyses.
+    ///
+    /// The seed is recorded in the saved estimates of the sample and of the change since the
+    /// baseline. It also seeds the bootstrap of the performance relative to a reference
+    /// benchmark.
+    pub fn rng_seed(mut self, seed: u64) -> Criterion<M> {
+        self.rng_seed = Some(seed);
+        self
+    }
+
     #[must_use]
     /// Enables plotting
     pub fn with_plots(mut self) -> Criterion<M> {
@@ -743,14 +1154,32 @@ impl<M: Measurement> Criterion<M> {
             plot_config: PlotConfiguration::default(),
         };
 
+        for corrected in correction::correct() {
+            self.report
+                .multiple_comparisons(&report_context, &corrected);
+        }
+        let relative = reference::take();
+        if !relative.is_empty() {
+            self.report.relative_performance(&report_context, &relative);
+        }
         self.report.final_summary(&report_context);
     }
 
     /// Configure this criterion struct based on the command-line arguments to
     /// this process.
     #[must_use]
+    pub fn configure_from_args(self) -> Criterion<M> {
+        self.configure_from(std::env::args_os())
+    }
+
+    /// Configure this criterion struct based on the given command-line arguments, the first of
+    /// which is the name of the executable.
     #[allow(clippy::cognitive_complexity)]
-    pub fn configure_from_args(mut self) -> Criterion<M> {
+    fn configure_from<I, T>(mut self, args: I) -> Criterion<M>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
         use clap::{value_parser, Arg, Command};
         let matches = Command::new("Criterion Benchmark")
             .arg(Arg::new("FILTER")
@@ -860,6 +1289,80 @@ impl<M: Measurement> Criterion<M> {
                 .num_args(0)
                 .conflicts_with("sample-size")
                 .help(format!("Benchmark only until the significance level has been reached [default: {}]", self.config.quick_mode)))
+            .arg(Arg::new("adaptive-warm-up")
+                .long("adaptive-warm-up")
+                .num_args(0)
+                .help("End the warm-up as soon as the benchmark reaches a steady state, using the warm-up time as an upper limit."))
+            .arg(Arg::new("target-precision")
+                .long("target-precision")
+                .value_parser(value_parser!(f64))
+                .conflicts_with("quick")
+                .help("Keep sampling until the confidence interval of the typical estimate is narrower than this relative width (eg. 0.01 for ±1%), up to the measurement time."))
+            .arg(Arg::new("comparison-test")
+                .long("comparison-test")
+                .value_parser(["t-test", "mann-whitney", "permutation"])
+                .help("Changes the hypothesis test used to compare against the baseline. [default: t-test]"))
+            .arg(Arg::new("comparison-report")
+                .long("comparison-report")
+                .value_parser(["p-value", "probability", "both"])
+                .help("Report the p-value of the comparison against the baseline, the probability of a change larger than the noise threshold, or both. [default: p-value]"))
+            .arg(Arg::new("equivalence-margin")
+                .long("equivalence-margin")
+                .value_parser(value_parser!(f64))
+                .help("Test whether each benchmark is equivalent to the baseline within this relative margin (eg. 0.02 for ±2%), using two one-sided tests."))
+            .arg(Arg::new("target-effect")
+                .long("target-effect")
+                .value_parser(value_parser!(f64))
+                .help("Changes the relative change that the power analysis estimates the required sample size and measurement time for (eg. 0.01 for 1%). [default: the noise threshold]"))
+            .arg(Arg::new("tracked-statistic")
+                .long("tracked-statistic")
+                .value_parser(TrackedStatistic::parse)
+                .help("Changes the statistic that decides whether a benchmark has changed: mean, median, min, trimmed-mean or a percentile such as p99. [default: mean]"))
+            .arg(Arg::new("outliers")
+                .long("outliers")
+                .value_parser(["tukey", "mad", "none"])
+                .help("Changes how outliers are identified: Tukey's fences (tukey), modified z-scores based on the median absolute deviation (mad), or not at all (none). [default: tukey]"))
+            .arg(Arg::new("learn-noise-threshold")
+                .long("learn-noise-threshold")
+                .num_args(0)
+                .help("Learn the noise threshold of each benchmark from the changes recorded in its previous comparisons."))
+            .arg(Arg::new("exclude-severe-outliers")
+                .long("exclude-severe-outliers")
+                .num_args(0)
+                .help("Exclude severe outliers from the estimates and comparisons."))
+            .arg(Arg::new("correction")
+                .long("correction")
+                .value_parser(["none", "holm", "bh"])
+                .help("Correct the comparisons against the baseline for the number of benchmarks compared in the run, using the Holm-Bonferroni (holm) or Benjamini-Hochberg (bh) method. [default: none]"))
+            .arg(Arg::new("runs")
+                .long("runs")
+                .value_parser(value_parser!(usize))
+                .help(format!("Repeat the warm-up and sampling of each benchmark this many times and pool the runs. [default: {}]", self.config.repetitions)))
+            .arg(Arg::new("pin-cpu")
+                .long("pin-cpu")
+                .value_parser(value_parser!(usize))
+                .help("Pin the benchmark thread to the given CPU core while measuring. Only supported on Linux."))
+            .arg(Arg::new("nice")
+                .long("nice")
+                .value_parser(value_parser!(i32))
+                .allow_negative_numbers(true)
+                .help("Set the scheduling priority (nice value) of the benchmark thread while measuring. Only supported on Linux."))
+            .arg(Arg::new("strict-env")
+                .long("strict-env")
+                .num_args(0)
+                .help("Refuse to run benchmarks if the system environment is noisy (eg. power-saving CPU governor, high load, running on battery, debug build). Turbo boost is only a warning."))
+            .arg(Arg::new("shuffle")
+                .long("shuffle")
+                .num_args(0)
+                .help("Run the benchmarks in a random order. The seed used is printed so that the order can be reproduced."))
+            .arg(Arg::new("shuffle-seed")
+                .long("shuffle-seed")
+                .value_parser(value_parser!(u64))
+                .help("Run the benchmarks in a random order determined by the given seed."))
+            .arg(Arg::new("seed")
+                .long("seed")
+                .value_parser(value_parser!(u64))
+                .help("Seed the random number generator of the statistical analysis, so that analyzing the same measurements gives identical results."))
             .arg(Arg::new("test")
                 .hide(true)
                 .long("test")
@@ -912,7 +1415,7 @@ To test that the benchmarks work, run `cargo test --benches`
 NOTE: If you see an 'unrecognized option' error using any of the options above, see:
 https://bheisler.github.io/criterion.rs/book/faq.html
 ")
-            .get_matches();
+            .get_matches_from(args);
 
         if self.connection.is_some() {
             if let Some(color) = matches.get_one::<String>("color") {
@@ -1121,6 +1624,106 @@ https://bheisler.github.io/criterion.rs/book/faq.html
             self.config.quick_mode = true;
         }
 
+        if matches.get_flag("adaptive-warm-up") {
+            self.config.adaptive_warm_up = true;
+        }
+
+        if let Some(&precision) = matches.get_one("target-precision") {
+            assert!(precision > 0.0);
+
+            self.config.target_precision = Some(precision);
+        }
+
+        if let Some(test) = matches.get_one::<String>("comparison-test") {
+            self.config.comparison_test = match test.as_str() {
+                "t-test" => ComparisonTest::TTest,
+                "mann-whitney" => ComparisonTest::MannWhitneyU,
+                "permutation" => ComparisonTest::Permutation,
+                _ => unreachable!(),
+            };
+        }
+
+        if let Some(report) = matches.get_one::<String>("comparison-report") {
+            self.config.comparison_report = match report.as_str() {
+                "p-value" => ComparisonReport::PValue,
+                "probability" => ComparisonReport::Probability,
+                "both" => ComparisonReport::Both,
+                _ => unreachable!(),
+            };
+        }
+
+        if let Some(&margin) = matches.get_one("equivalence-margin") {
+            assert!(margin > 0.0);
+
+            self.config.equivalence_margin = Some(margin);
+        }
+
+        if let Some(&effect) = matches.get_one("target-effect") {
+            assert!(effect > 0.0);
+
+            self.config.target_effect = Some(effect);
+        }
+
+        if let Some(&statistic) = matches.get_one::<TrackedStatistic>("tracked-statistic") {
+            self.config.tracked_statistic = statistic;
+        }
+
+        if let Some(outliers) = matches.get_one::<String>("outliers") {
+            self.config.outlier_detection = match outliers.as_str() {
+                "tukey" => OutlierDetection::default(),
+                "mad" => OutlierDetection::ModifiedZScore {
+                    mild: 3.5,
+                    severe: 5.0,
+                },
+                "none" => OutlierDetection::None,
+                _ => unreachable!(),
+            };
+        }
+
+        if matches.get_flag("learn-noise-threshold") {
+            self.config.learn_noise_threshold = true;
+        }
+
+        if matches.get_flag("exclude-severe-outliers") {
+            self.config.exclude_severe_outliers = true;
+        }
+
+        if let Some(correction) = matches.get_one::<String>("correction") {
+            self.correction = match correction.as_str() {
+                "none" => MultipleComparisonCorrection::None,
+                "holm" => MultipleComparisonCorrection::Holm,
+                "bh" => MultipleComparisonCorrection::BenjaminiHochberg,
+                _ => unreachable!(),
+            };
+        }
+
+        if let Some(&num_runs) = matches.get_one("runs") {
+            assert!(num_runs > 0);
+
+            self.config.repetitions = num_runs;
+        }
+
+        if matches.get_flag("strict-env") {
+            self.strict_environment = true;
+        }
+
+        if let Some(&seed) = matches.get_one("seed") {
+            self.rng_seed = Some(seed);
+        }
+
+        if let Some(&seed) = matches.get_one("shuffle-seed") {
+            self.shuffle_seed = Some(seed);
+        } else if matches.get_flag("shuffle") {
+            self.shuffle_seed = Some(random_shuffle_seed());
+        }
+
+        if let Some(&core) = matches.get_one("pin-cpu") {
+            self.scheduling.cpu = Some(core);
+        }
+        if let Some(&nice) = matches.get_one("nice") {
+            self.scheduling.nice = Some(nice);
+        }
+
         self
     }
 
@@ -1141,6 +1744,43 @@ https://bheisler.github.io/criterion.rs/book/faq.html
             && !matches!(self.baseline, Baseline::Discard)
     }
 
+    /// Runs the targets of a `criterion_group!`, shuffling them first if requested. Should not
+    /// be called directly.
+    #[doc(hidden)]
+    pub fn run_targets(&mut self, targets: &mut [fn(&mut Criterion<M>)]) {
+        if let Some(seed) = self.shuffle_seed {
+            if self.report.cli_enabled {
+                announce_shuffle_seed(seed);
+            }
+            shuffle_with_seed(seed, targets);
+        }
+        for target in targets.iter() {
+            target(self);
+        }
+    }
+
+    /// Reports the results of the system preflight checks (once per process) and exits if the
+    /// environment is noisy and strict checking is enabled. Nothing is checked when the
+    /// measurements are loaded from a saved baseline, as none are taken.
+    fn check_environment(&self) {
+        static REPORTED: Once = Once::new();
+
+        if self.load_baseline.is_some() {
+            return;
+        }
+
+        let environment = system_environment();
+        REPORTED.call_once(|| self.report.environment(environment));
+
+        if self.strict_environment && environment.is_noisy() {
+            eprintln!("Error: refusing to run benchmarks in a noisy environment (--strict-env):");
+            for warning in environment.refusals() {
+                eprintln!("  {}", warning);
+            }
+            std::process::exit(1);
+        }
+    }
+
     /// Return a benchmark group. All benchmarks performed using a benchmark group will be
     /// grouped together in the final report.
     ///
@@ -1292,6 +1932,46 @@ pub enum AxisScale {
     Logarithmic,
 }
 
+/// The kernel used to estimate the probability density of the measurements in the PDF plots.
+/// Specified via [`PlotConfiguration::kde_kernel`].
+///
+/// All kernels are scaled to unit variance, so the same bandwidth gives a similar amount of
+/// smoothing with each of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KdeKernel {
+    /// The Gaussian kernel. This is the default.
+    #[default]
+    Gaussian,
+
+    /// The Epanechnikov (parabolic) kernel
+    Epanechnikov,
+
+    /// The triangular kernel
+    Triangular,
+
+    /// The uniform (rectangular) kernel
+    Uniform,
+}
+
+/// The method used to choose the bandwidth of the kernel density estimate in the PDF plots.
+/// Specified via [`PlotConfiguration::kde_bandwidth`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum KdeBandwidth {
+    /// Silverman's rule of thumb. This is the default. It assumes that the measurements are
+    /// normally distributed, so it oversmooths multimodal distributions.
+    #[default]
+    Silverman,
+
+    /// Scott's rule of thumb. This smooths slightly less than Silverman's rule, but makes the
+    /// same assumption.
+    Scott,
+
+    /// The Sheather-Jones plug-in method, which estimates the shape of the distribution from the
+    /// measurements. It keeps the separate peaks of a multimodal distribution visible, at the
+    /// cost of a noisier curve. Falls back to Silverman's rule for very small or sparse samples.
+    SheatherJones,
+}
+
 /// Contains the configuration options for the plots generated by a particular benchmark
 /// or benchmark group.
 ///
@@ -1310,6 +1990,8 @@ pub enum AxisScale {
 #[derive(Debug, Default, Clone)]
 pub struct PlotConfiguration {
     summary_scale: AxisScale,
+    kde_kernel: KdeKernel,
+    kde_bandwidth: KdeBandwidth,
 }
 
 impl PlotConfiguration {
@@ -1325,12 +2007,293 @@ impl PlotConfiguration {
         self.summary_scale = new_scale;
         self
     }
+
+    #[must_use]
+    /// Set the kernel used to estimate the probability density of the measurements in the PDF
+    /// plots and when looking for multiple modes. Defaults to [`KdeKernel::Gaussian`].
+    pub fn kde_kernel(mut self, kernel: KdeKernel) -> PlotConfiguration {
+        self.kde_kernel = kernel;
+        self
+    }
+
+    #[must_use]
+    /// Set the method used to choose the bandwidth of the density estimate in the PDF plots and
+    /// when looking for multiple modes. Defaults to [`KdeBandwidth::Silverman`].
+    ///
+    /// Silverman's rule tends to smooth over the separate peaks of a multimodal distribution;
+    /// [`KdeBandwidth::SheatherJones`] keeps them visible.
+    pub fn kde_bandwidth(mut self, bandwidth: KdeBandwidth) -> PlotConfiguration {
+        self.kde_bandwidth = bandwidth;
+        self
+    }
+}
+
+/// The hypothesis test used to decide whether a benchmark has changed since the baseline.
+/// The default is `TTest`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComparisonTest {
+    /// Welch's t-test on the mean time per iteration, with the distribution of the t statistic
+    /// estimated by bootstrapping. This is the default.
+    #[default]
+    TTest,
+
+    /// The Mann-Whitney U (rank-sum) test. This makes no assumption about the shape of the
+    /// distributions, and is robust to outliers and heavy tails.
+    MannWhitneyU,
+
+    /// A permutation test on the difference of the means, approximated by randomly relabelling
+    /// the pooled measurements as many times as the number of bootstrap resamples.
+    Permutation,
+}
+
+impl ComparisonTest {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            ComparisonTest::TTest => "t-test",
+            ComparisonTest::MannWhitneyU => "Mann-Whitney U",
+            ComparisonTest::Permutation => "permutation test",
+        }
+    }
+}
+
+/// How the evidence for a change since the baseline is reported. The default is `PValue`.
+///
+/// This only changes what is reported; whether a benchmark is reported as improved or regressed
+/// is still decided by the hypothesis test and the noise threshold.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComparisonReport {
+    /// Report the p-value of the hypothesis test. This is the default.
+    #[default]
+    PValue,
+
+    /// Report the probability that the change is a regression (or an improvement) larger than the
+    /// noise threshold, instead of the p-value.
+    Probability,
+
+    /// Report both the p-value and the probability.
+    Both,
+}
+
+/// The linear regression whose slope is used as the typical time per iteration, when the
+/// sampling mode varies the iteration count. The default is `ThroughOrigin`.
+///
+/// Both models are always fitted, and their estimates are saved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegressionModel {
+    /// A line through the origin, `time = slope * iterations`. This is the default.
+    #[default]
+    ThroughOrigin,
+
+    /// A line with an intercept, `time = intercept + slope * iterations`. The intercept absorbs
+    /// the fixed overhead of each sample, such as reading the clock and entering the loop,
+    /// which would otherwise leak into the time per iteration of very fast benchmarks.
+    WithIntercept,
+}
+
+/// The method used to compute the confidence intervals of the estimates from their bootstrap
+/// distributions. The default is `Percentile`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfidenceIntervalMethod {
+    /// Use the percentiles of the bootstrap distribution directly. This is the default.
+    #[default]
+    Percentile,
+
+    /// Bias-corrected and accelerated intervals. These shift the percentiles to correct
+    /// for the bootstrap distribution being off-centre and skewed, which makes them more accurate
+    /// for the skewed distributions typical of timings. Estimating the acceleration needs a
+    /// jackknife over the sample, which makes the analysis slower for large samples.
+    Bca,
+}
+
+/// A statistic of the time per iteration. Besides the mean and median, which are always estimated,
+/// the minimum, a trimmed mean and percentiles can be estimated on request, and any of them can
+/// decide whether a benchmark has changed. The default is `Mean`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrackedStatistic {
+    /// The mean time per iteration. This is the default.
+    #[default]
+    Mean,
+
+    /// The median time per iteration
+    Median,
+
+    /// The shortest time per iteration. This is the least affected by interference from the rest
+    /// of the system, but also the least representative of the time in normal use.
+    ///
+    /// The bootstrap is a poor fit for the minimum: a resample can't have a shorter minimum than
+    /// the sample, so the bootstrap distribution only takes a handful of values and is skewed
+    /// away from the shorter times. Its confidence interval and p-value are unreliable, and a low
+    /// percentile such as `Percentile(5)` is a better choice for tracking the best case.
+    Min,
+
+    /// The mean of the times per iteration after discarding the shortest and longest 10%
+    TrimmedMean,
+
+    /// The given percentile of the times per iteration, between 1 and 99 (eg. 90 for p90)
+    Percentile(u8),
+}
+
+impl TrackedStatistic {
+    /// The proportion of the measurements discarded from each end for the trimmed mean
+    const TRIM: f64 = 0.1;
+
+    fn validate(self) {
+        if let TrackedStatistic::Percentile(p) = self {
+            assert!(
+                (1..=99).contains(&p),
+                "Percentiles must be between 1 and 99"
+            );
+        }
+    }
+
+    /// Parses the name of a statistic given on the command line
+    fn parse(name: &str) -> Result<TrackedStatistic, String> {
+        match name {
+            "mean" => Ok(TrackedStatistic::Mean),
+            "median" => Ok(TrackedStatistic::Median),
+            "min" => Ok(TrackedStatistic::Min),
+            "trimmed-mean" => Ok(TrackedStatistic::TrimmedMean),
+            _ => name
+                .strip_prefix('p')
+                .and_then(|p| p.parse().ok())
+                .filter(|p| (1..=99).contains(p))
+                .map(TrackedStatistic::Percentile)
+                .ok_or_else(|| {
+                    format!(
+                        "expected mean, median, min, trimmed-mean or a percentile from p1 to p99, found {}",
+                        name
+                    )
+                }),
+        }
+    }
+
+    /// Whether the statistic is only estimated on request
+    pub(crate) fn is_extra(self) -> bool {
+        !matches!(self, TrackedStatistic::Mean | TrackedStatistic::Median)
+    }
+
+    pub(crate) fn evaluate(self, sample: &Sample<f64>) -> f64 {
+        match self {
+            TrackedStatistic::Mean => sample.mean(),
+            TrackedStatistic::Median => sample.percentiles().median(),
+            TrackedStatistic::Min => sample.min(),
+            TrackedStatistic::TrimmedMean => sample.trimmed_mean(Self::TRIM),
+            TrackedStatistic::Percentile(p) => sample.percentiles().at(f64::from(p)),
+        }
+    }
+}
+
+impl fmt::Display for TrackedStatistic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TrackedStatistic::Mean => f.pad("mean"),
+            TrackedStatistic::Median => f.pad("median"),
+            TrackedStatistic::Min => f.pad("min"),
+            TrackedStatistic::TrimmedMean => f.pad("trimmed mean"),
+            TrackedStatistic::Percentile(p) => f.pad(&format!("p{}", p)),
+        }
+    }
+}
+
+/// How outliers are identified in the measurements of a benchmark. The default is Tukey's fences
+/// with the usual multipliers of 1.5 and 3.
+///
+/// Outliers are counted and shown in the plots. They are only excluded from the estimates if
+/// [`Criterion::exclude_severe_outliers`] is enabled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutlierDetection {
+    /// Tukey's fences. Measurements more than `mild` times the interquartile range below the first
+    /// quartile or above the third quartile are mild outliers, and those more than `severe` times
+    /// the interquartile range away are severe outliers.
+    Tukey {
+        /// Multiplier of the interquartile range for the mild fences
+        mild: f64,
+        /// Multiplier of the interquartile range for the severe fences
+        severe: f64,
+    },
+
+    /// Modified z-scores, ie. the distance from the median in units of the median absolute
+    /// deviation. Measurements whose modified z-score is larger than `mild` are mild outliers,
+    /// and those larger than `severe` are severe outliers. This is more robust than Tukey's
+    /// fences for skewed distributions.
+    ModifiedZScore {
+        /// Threshold of the modified z-score for mild outliers
+        mild: f64,
+        /// Threshold of the modified z-score for severe outliers
+        severe: f64,
+    },
+
+    /// Don't classify any measurement as an outlier.
+    None,
+}
+
+impl Default for OutlierDetection {
+    fn default() -> OutlierDetection {
+        OutlierDetection::Tukey {
+            mild: 1.5,
+            severe: 3.0,
+        }
+    }
+}
+
+impl OutlierDetection {
+    fn validate(&self) {
+        match *self {
+            OutlierDetection::Tukey { mild, severe }
+            | OutlierDetection::ModifiedZScore { mild, severe } => {
+                assert!(
+                    mild > 0.0 && severe >= mild,
+                    "The outlier thresholds must be positive, and the severe threshold must not be \
+                    smaller than the mild one"
+                );
+            }
+            OutlierDetection::None => {}
+        }
+    }
+
+    pub(crate) fn classify<'a>(&self, sample: &'a Sample<f64>) -> LabeledSample<'a, f64> {
+        match *self {
+            OutlierDetection::Tukey { mild, severe } => tukey::classify_with(sample, mild, severe),
+            OutlierDetection::ModifiedZScore { mild, severe } => {
+                mad::classify(sample, mild, severe)
+            }
+            OutlierDetection::None => LabeledSample::without_outliers(sample),
+        }
+    }
+}
+
+/// How to correct the comparisons against the baseline for the number of benchmarks compared in
+/// a run. The default is `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultipleComparisonCorrection {
+    /// Don't correct the comparisons. This is the default.
+    #[default]
+    None,
+
+    /// The Holm-Bonferroni method, which controls the probability of reporting even one change
+    /// that didn't happen.
+    Holm,
+
+    /// The Benjamini-Hochberg method, which controls the expected fraction of the reported
+    /// changes that didn't happen. This is less conservative than `Holm`, and better suited to
+    /// large numbers of benchmarks.
+    BenjaminiHochberg,
+}
+
+impl MultipleComparisonCorrection {
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            MultipleComparisonCorrection::None => "none",
+            MultipleComparisonCorrection::Holm => "Holm-Bonferroni",
+            MultipleComparisonCorrection::BenjaminiHochberg => "Benjamini-Hochberg",
+        }
+    }
 }
 
 /// This enum allows the user to control how Criterion.rs chooses the iteration count when sampling.
 /// The default is `Auto`, which will choose a method automatically based on the iteration time during
 /// the warm-up phase.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 pub enum SamplingMode {
     /// Criterion.rs should choose a sampling method automatically. This is the default, and is
     /// recommended for most users and most benchmarks.
@@ -1346,6 +2309,16 @@ pub enum SamplingMode {
     /// the `Linear` method and therefore is more suitable for very long-running benchmarks where
     /// benchmark execution time is more of a problem and statistical precision is less important.
     Flat,
+
+    /// Scale the iteration count in each sample geometrically, covering the same range of
+    /// iteration counts as `Linear`. Samples are spread evenly on a logarithmic scale, so more of
+    /// the measurement time is spent on small and medium batches rather than on the largest ones.
+    Geometric,
+
+    /// Use exactly the given iteration count for each sample, in order. This ignores the sample
+    /// size and measurement time, and is useful to reproduce a run exactly. The schedule must
+    /// contain at least two iteration counts, none of which may be zero.
+    Custom(Vec<u64>),
 }
 
 impl SamplingMode {
@@ -1358,6 +2331,8 @@ impl SamplingMode {
         match self {
             SamplingMode::Linear => ActualSamplingMode::Linear,
             SamplingMode::Flat => ActualSamplingMode::Flat,
+            SamplingMode::Geometric => ActualSamplingMode::Geometric,
+            SamplingMode::Custom(counts) => ActualSamplingMode::Custom(counts.clone()),
             SamplingMode::Auto => {
                 // Estimate execution time with linear sampling
                 let total_runs = sample_count * (sample_count + 1) / 2;
@@ -1373,13 +2348,29 @@ impl SamplingMode {
             }
         }
     }
+
+    /// Checks that a custom schedule can be used for sampling.
+    fn validate(&self) {
+        if let SamplingMode::Custom(counts) = self {
+            assert!(
+                counts.len() >= 2,
+                "A custom sampling schedule must contain at least two iteration counts"
+            );
+            assert!(
+                counts.iter().all(|&count| count > 0),
+                "A custom sampling schedule must not contain an iteration count of zero"
+            );
+        }
+    }
 }
 
 /// Enum to represent the sampling mode without Auto.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum ActualSamplingMode {
     Linear,
     Flat,
+    Geometric,
+    Custom(Vec<u64>),
 }
 
 impl ActualSamplingMode {
@@ -1444,11 +2435,55 @@ impl ActualSamplingMode {
 
                 vec![iterations_per_sample; n as usize]
             }
+            ActualSamplingMode::Geometric => {
+                let n = sample_count;
+                let met = warmup_mean_execution_time;
+                let m_ns = target_time.as_nanos() as f64;
+                // Grow by a constant ratio from d to n*d, the same range as linear sampling.
+                let ratio = (n as f64).powf(1.0 / (n.max(2) - 1) as f64);
+                let weights = (0..n).map(|i| ratio.powi(i as i32)).collect::<Vec<f64>>();
+                // Solve: [w_0*d + w_1*d + ... + w_(n-1)*d] * met = m_ns
+                let d = m_ns / met / weights.iter().sum::<f64>();
+                let counts = weights
+                    .iter()
+                    .map(|w| ((w * d).round() as u64).max(1))
+                    .collect::<Vec<u64>>();
+
+                if d < 1.0 {
+                    let expected_ns = counts.iter().sum::<u64>() as f64 * met;
+                    let actual_time = Duration::from_nanos(expected_ns as u64);
+                    eprintln!("\nWarning: Unable to complete {} samples in {:.1?}. You may wish to increase target time to {:.1?}, enable flat sampling, or reduce sample count.",
+                            n, target_time, actual_time);
+                }
+
+                counts
+            }
+            ActualSamplingMode::Custom(counts) => counts.clone(),
         }
     }
 
+    /// Returns true if the iteration count varies between samples, so that the time per
+    /// iteration can be estimated by regression.
     fn is_linear(&self) -> bool {
-        matches!(self, ActualSamplingMode::Linear)
+        !matches!(self, ActualSamplingMode::Flat)
+    }
+
+    /// Returns a quantity proportional to the total number of iterations of a sample of the given
+    /// size, if the smallest iteration count stays the same. Custom iteration counts are assumed
+    /// to be repeated, keeping their mean.
+    fn total_iterations(&self, sample_count: usize) -> f64 {
+        let n = sample_count as f64;
+        match self {
+            ActualSamplingMode::Flat => n,
+            ActualSamplingMode::Linear => n * (n + 1.0) / 2.0,
+            ActualSamplingMode::Geometric => {
+                let ratio = n.powf(1.0 / (sample_count.max(2) - 1) as f64);
+                (0..sample_count).map(|i| ratio.powi(i as i32)).sum()
+            }
+            ActualSamplingMode::Custom(counts) => {
+                n * counts.iter().sum::<u64>() as f64 / counts.len() as f64
+            }
+        }
     }
 
     fn recommend_linear_sample_size(target_time: f64, met: f64) -> u64 {
@@ -1494,6 +2529,41 @@ pub(crate) struct SavedSample {
     sampling_mode: ActualSamplingMode,
     iters: Vec<f64>,
     times: Vec<f64>,
+    #[serde(default, flatten)]
+    scheduling: AppliedScheduling,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    environment: Option<EnvironmentReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shuffle_seed: Option<u64>,
+    /// The number of measurements in each independent run, if there was more than one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    run_lengths: Vec<usize>,
+}
+impl SavedSample {
+    /// Returns the number of measurements in each run. A sample taken in a single run (or saved
+    /// by an older version) is one run of all of the measurements.
+    fn runs(&self) -> Vec<usize> {
+        if self.run_lengths.is_empty() {
+            vec![self.iters.len()]
+        } else {
+            self.run_lengths.clone()
+        }
+    }
+}
+
+/// Runs the groups of a `criterion_main!`, shuffling them first if requested. Should not be called
+/// directly.
+#[doc(hidden)]
+pub fn run_groups(groups: &mut [fn()]) {
+    // The seed is parsed along with the rest of the arguments, exactly as each group will parse
+    // them; a random seed is chosen once per process, so the groups agree on it. The first group
+    // to run prints it if the CLI output is enabled.
+    if let Some(seed) = Criterion::default().configure_from_args().shuffle_seed {
+        shuffle_with_seed(seed, groups);
+    }
+    for group in groups.iter() {
+        group();
+    }
 }
 
 /// Custom-test-framework runner. Should not be called directly.
@@ -1504,3 +2574,22 @@ pub fn runner(benches: &[&dyn Fn()]) {
     }
     Criterion::default().configure_from_args().final_summary();
 }
+
+#[cfg(test)]
+mod test {
+    use super::{random_shuffle_seed, Criterion};
+
+    fn shuffle_seed(args: &[&str]) -> Option<u64> {
+        let args = std::iter::once("bench").chain(args.iter().copied());
+        Criterion::default().configure_from(args).shuffle_seed
+    }
+
+    #[test]
+    fn reads_shuffle_seed_from_args() {
+        assert_eq!(None, shuffle_seed(&["--bench", "filter"]));
+        assert_eq!(Some(42), shuffle_seed(&["--bench", "--shuffle-seed", "42"]));
+        assert_eq!(Some(42), shuffle_seed(&["--shuffle-seed=42", "--noplot"]));
+        assert_eq!(Some(random_shuffle_seed()), shuffle_seed(&["--shuffle"]));
+        assert_eq!(None, shuffle_seed(&["--", "--shuffle"]));
+    }
+}
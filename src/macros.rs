@@ -64,9 +64,7 @@ macro_rules! criterion_group {
         pub fn $name() {
             let mut criterion: $crate::Criterion<_> = $config
                 .configure_from_args();
-            $(
-                $target(&mut criterion);
-            )+
+            criterion.run_targets(&mut [$( $target ),+]);
         }
     };
     ($name:ident, $( $target:path ),+ $(,)*) => {
@@ -115,9 +113,7 @@ macro_rules! criterion_group {
 macro_rules! criterion_main {
     ( $( $group:path ),+ $(,)* ) => {
         fn main() {
-            $(
-                $group();
-            )+
+            $crate::run_groups(&mut [$( $group ),+]);
 
             $crate::Criterion::default()
                 .configure_from_args()
@@ -0,0 +1,137 @@
+//! Noise thresholds learned from the history of each benchmark
+//!
+//! Every comparison against a saved baseline records the relative change in the tracked
+//! statistic. Most of these changes are run-to-run noise, so the spread of the recent changes
+//! estimates how much the benchmark varies between runs when nothing has changed. A change is
+//! only considered significant if it's well beyond that spread, which lets stable benchmarks
+//! detect small changes while keeping noisy benchmarks from reporting false regressions.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::fs;
+use crate::report::BenchmarkId;
+use crate::stats::univariate::Sample;
+use crate::TrackedStatistic;
+
+/// The number of recent changes kept for each statistic.
+const HISTORY_LENGTH: usize = 30;
+
+/// The number of recorded changes needed before a threshold is learned.
+const MIN_HISTORY: usize = 5;
+
+/// How many (robust) standard deviations of the recorded changes the threshold is set at.
+const NOISE_SIGMAS: f64 = 3.0;
+
+/// The smallest threshold that is ever learned, as timing is never more precise than this.
+const MIN_THRESHOLD: f64 = 0.001;
+
+/// A learned noise threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LearnedThreshold {
+    pub threshold: f64,
+    /// The number of recorded changes it was learned from
+    pub history: usize,
+}
+
+/// The recent relative changes of a benchmark, by the name of the statistic that changed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct NoiseHistory {
+    changes: BTreeMap<String, Vec<f64>>,
+}
+impl NoiseHistory {
+    fn path(output_directory: &Path, id: &BenchmarkId) -> PathBuf {
+        output_directory
+            .join(id.as_directory_name())
+            .join("noise_history.json")
+    }
+
+    /// Loads the history of a benchmark, which is empty if none has been saved yet.
+    pub fn load(output_directory: &Path, id: &BenchmarkId) -> NoiseHistory {
+        fs::load(&Self::path(output_directory, id)).unwrap_or_default()
+    }
+
+    pub fn save(&self, output_directory: &Path, id: &BenchmarkId) -> Result<()> {
+        fs::save(self, &Self::path(output_directory, id))
+    }
+
+    /// Records a relative change in a statistic, forgetting the oldest changes beyond the length
+    /// of the history.
+    pub fn record(&mut self, statistic: TrackedStatistic, change: f64) {
+        let changes = self.changes.entry(statistic.to_string()).or_default();
+        changes.push(change);
+        if changes.len() > HISTORY_LENGTH {
+            changes.drain(..changes.len() - HISTORY_LENGTH);
+        }
+    }
+
+    /// Learns the noise threshold of a statistic from its recorded changes, if there are enough
+    /// of them. The spread of the changes is estimated from their median absolute value, so the
+    /// occasional genuine change doesn't inflate it.
+    pub fn threshold(&self, statistic: TrackedStatistic) -> Option<LearnedThreshold> {
+        let changes = self.changes.get(&statistic.to_string())?;
+        if changes.len() < MIN_HISTORY {
+            return None;
+        }
+
+        let magnitudes = changes
+            .iter()
+            .map(|change| change.abs())
+            .collect::<Vec<_>>();
+        let sigma = Sample::new(&magnitudes).percentiles().median() * 1.4826;
+        Some(LearnedThreshold {
+            threshold: (NOISE_SIGMAS * sigma).max(MIN_THRESHOLD),
+            history: changes.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{NoiseHistory, HISTORY_LENGTH, MIN_HISTORY, MIN_THRESHOLD, NOISE_SIGMAS};
+    use crate::TrackedStatistic;
+
+    #[test]
+    fn needs_enough_history() {
+        let mut history = NoiseHistory::default();
+        for _ in 1..MIN_HISTORY {
+            history.record(TrackedStatistic::Mean, 0.01);
+        }
+        assert_eq!(None, history.threshold(TrackedStatistic::Mean));
+
+        history.record(TrackedStatistic::Mean, 0.01);
+        let learned = history.threshold(TrackedStatistic::Mean).unwrap();
+        assert_eq!(MIN_HISTORY, learned.history);
+        assert_relative_eq!(learned.threshold, NOISE_SIGMAS * 0.01 * 1.4826);
+        assert_eq!(None, history.threshold(TrackedStatistic::Median));
+    }
+
+    #[test]
+    fn ignores_occasional_changes() {
+        let mut history = NoiseHistory::default();
+        for change in [0.002, -0.002, 0.002, -0.002, 0.25, -0.002, 0.002] {
+            history.record(TrackedStatistic::Mean, change);
+        }
+        let learned = history.threshold(TrackedStatistic::Mean).unwrap();
+        assert_relative_eq!(learned.threshold, NOISE_SIGMAS * 0.002 * 1.4826);
+    }
+
+    #[test]
+    fn keeps_recent_changes() {
+        let mut history = NoiseHistory::default();
+        for _ in 0..HISTORY_LENGTH {
+            history.record(TrackedStatistic::Mean, 0.1);
+        }
+        for _ in 0..HISTORY_LENGTH {
+            history.record(TrackedStatistic::Mean, 0.0);
+        }
+        let learned = history.threshold(TrackedStatistic::Mean).unwrap();
+        assert_eq!(HISTORY_LENGTH, learned.history);
+        assert_relative_eq!(learned.threshold, MIN_THRESHOLD);
+    }
+}
@@ -0,0 +1,81 @@
+use criterion_plot::prelude::*;
+
+use super::*;
+use crate::report::{MeasurementData, ReportContext};
+
+pub(crate) fn iteration_order(
+    id: &BenchmarkId,
+    context: &ReportContext,
+    formatter: &dyn ValueFormatter,
+    measurements: &MeasurementData<'_>,
+    size: Option<Size>,
+) -> Child {
+    let data = &measurements.avg_times;
+    let max_avg_time = data.max();
+    let mut scaled_y: Vec<_> = data.iter().map(|(f, _)| f).collect();
+    let unit = formatter.scale_values(max_avg_time, &mut scaled_y);
+    let scaled_y = Sample::new(&scaled_y);
+
+    let mut figure = Figure::new();
+    figure
+        .set(Font(DEFAULT_FONT))
+        .set(size.unwrap_or(SIZE))
+        .configure(Axis::BottomX, |a| {
+            a.configure(Grid::Major, |g| g.show())
+                .set(Label("Sample (in the order measured)"))
+        })
+        .configure(Axis::LeftY, |a| {
+            a.configure(Grid::Major, |g| g.show())
+                .set(Label(format!("Average Iteration Time ({})", unit)))
+        })
+        .configure(Key, |k| {
+            k.set(Justification::Left)
+                .set(Order::SampleText)
+                .set(Position::Inside(Vertical::Top, Horizontal::Left))
+        })
+        .plot(
+            LinesPoints {
+                x: 1..(data.len() + 1),
+                y: scaled_y.as_ref(),
+            },
+            |c| {
+                c.set(DARK_BLUE)
+                    .set(Label("Sample"))
+                    .set(PointSize(0.5))
+                    .set(PointType::FilledCircle)
+            },
+        );
+
+    // Show the means on either side of a shift in the measurements
+    if let Some(changepoint) = &measurements.order.changepoint {
+        let mut means = [changepoint.mean_before, changepoint.mean_after];
+        let _ = formatter.scale_values(max_avg_time, &mut means);
+        let [before, after] = means;
+        let shift = changepoint.index as f64 + 0.5;
+
+        figure
+            .plot(
+                Lines {
+                    x: &[1., shift],
+                    y: &[before, before],
+                },
+                |c| {
+                    c.set(DARK_RED)
+                        .set(LINEWIDTH)
+                        .set(Label("Mean before and after the shift"))
+                },
+            )
+            .plot(
+                Lines {
+                    x: &[shift, data.len() as f64],
+                    y: &[after, after],
+                },
+                |c| c.set(DARK_RED).set(LINEWIDTH),
+            );
+    }
+    figure.set(Title(gnuplot_escape(id.as_title())));
+
+    let path = context.report_path(id, "iteration_order.svg");
+    debug_script(&path, &figure);
+    figure.set(Output(path)).draw().unwrap()
+}
@@ -6,12 +6,14 @@ use crate::stats::univariate::Sample;
 use criterion_plot::prelude::*;
 
 mod distributions;
+mod iteration_order;
 mod iteration_times;
 mod pdf;
 mod regression;
 mod summary;
 mod t_test;
 use self::distributions::*;
+use self::iteration_order::*;
 use self::iteration_times::*;
 use self::pdf::*;
 use self::regression::*;
@@ -39,6 +41,7 @@ const POINT_SIZE: PointSize = PointSize(0.75);
 const DARK_BLUE: Color = Color::Rgb(31, 120, 180);
 const DARK_ORANGE: Color = Color::Rgb(255, 127, 0);
 const DARK_RED: Color = Color::Rgb(227, 26, 28);
+const DARK_GREEN: Color = Color::Rgb(51, 160, 44);
 
 fn debug_script(path: &Path, figure: &Figure) {
     if crate::debug_enabled() {
@@ -120,6 +123,17 @@ impl Plotter for Gnuplot {
         });
     }
 
+    fn iteration_order(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>) {
+        let size = ctx.size.map(|(w, h)| Size(w, h));
+        self.process_list.push(iteration_order(
+            ctx.id,
+            ctx.context,
+            data.formatter,
+            data.measurements,
+            size,
+        ));
+    }
+
     fn iteration_times(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>) {
         let size = ctx.size.map(|(w, h)| Size(w, h));
         self.process_list.push(if ctx.is_thumbnail {
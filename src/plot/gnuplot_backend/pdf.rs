@@ -1,6 +1,7 @@
 use super::*;
 use crate::kde;
 use crate::report::{ComparisonData, MeasurementData, ReportContext};
+use crate::PlotConfiguration;
 
 pub(crate) fn pdf(
     id: &BenchmarkId,
@@ -31,7 +32,13 @@ pub(crate) fn pdf(
         format!("Iterations (x 10^{})", exponent)
     };
 
-    let (xs, ys) = kde::sweep(scaled_avg_times, KDE_POINTS, None);
+    let (xs, ys) = kde::sweep_with(
+        scaled_avg_times,
+        KDE_POINTS,
+        None,
+        context.plot_config.kde_kernel,
+        context.plot_config.kde_bandwidth,
+    );
     let (lost, lomt, himt, hist) = avg_times.fences();
     let mut fences = [lost, lomt, himt, hist];
     let _ = formatter.scale_values(typical, &mut fences);
@@ -186,35 +193,46 @@ pub(crate) fn pdf(
                     .set(POINT_SIZE)
                     .set(PointType::FilledCircle)
             },
-        )
-        .plot(
-            Lines {
-                x: &[lomt, lomt],
-                y: vertical,
-            },
-            |c| c.set(DARK_ORANGE).set(LINEWIDTH).set(LineType::Dash),
-        )
-        .plot(
-            Lines {
-                x: &[himt, himt],
-                y: vertical,
-            },
-            |c| c.set(DARK_ORANGE).set(LINEWIDTH).set(LineType::Dash),
-        )
-        .plot(
-            Lines {
-                x: &[lost, lost],
-                y: vertical,
-            },
-            |c| c.set(DARK_RED).set(LINEWIDTH).set(LineType::Dash),
-        )
-        .plot(
-            Lines {
-                x: &[hist, hist],
-                y: vertical,
-            },
-            |c| c.set(DARK_RED).set(LINEWIDTH).set(LineType::Dash),
         );
+
+    // Fences are infinite if outlier detection is disabled
+    for (fence, color) in [
+        (lomt, DARK_ORANGE),
+        (himt, DARK_ORANGE),
+        (lost, DARK_RED),
+        (hist, DARK_RED),
+    ] {
+        if fence.is_finite() {
+            figure.plot(
+                Lines {
+                    x: &[fence, fence],
+                    y: vertical,
+                },
+                |c| c.set(color).set(LINEWIDTH).set(LineType::Dash),
+            );
+        }
+    }
+
+    // Mark the modes of a multimodal distribution
+    let mut modes = measurements.modes.clone();
+    let _ = formatter.scale_values(typical, &mut modes);
+    if modes.len() > 1 {
+        for (i, &mode) in modes.iter().enumerate() {
+            figure.plot(
+                Lines {
+                    x: &[mode, mode],
+                    y: vertical,
+                },
+                |c| {
+                    c.set(DARK_GREEN).set(LINEWIDTH).set(LineType::Dot);
+                    if i == 0 {
+                        c.set(Label("Modes"));
+                    }
+                    c
+                },
+            );
+        }
+    }
     figure.set(Title(gnuplot_escape(id.as_title())));
 
     let path = context.report_path(id, "pdf.svg");
@@ -236,7 +254,14 @@ pub(crate) fn pdf_small(
     let scaled_avg_times = Sample::new(&scaled_avg_times);
     let mean = scaled_avg_times.mean();
 
-    let (xs, ys, mean_y) = kde::sweep_and_estimate(scaled_avg_times, KDE_POINTS, None, mean);
+    let (xs, ys, mean_y) = kde::sweep_and_estimate(
+        scaled_avg_times,
+        KDE_POINTS,
+        None,
+        mean,
+        context.plot_config.kde_kernel,
+        context.plot_config.kde_bandwidth,
+    );
     let xs_ = Sample::new(&xs);
     let ys_ = Sample::new(&ys);
 
@@ -287,6 +312,7 @@ fn pdf_comparison_figure(
     formatter: &dyn ValueFormatter,
     measurements: &MeasurementData<'_>,
     comparison: &ComparisonData,
+    plot_config: &PlotConfiguration,
     size: Option<Size>,
 ) -> Figure {
     let base_avg_times = Sample::new(&comparison.base_avg_times);
@@ -305,10 +331,22 @@ fn pdf_comparison_figure(
     let base_mean = scaled_base_avg_times.mean();
     let new_mean = scaled_new_avg_times.mean();
 
-    let (base_xs, base_ys, base_y_mean) =
-        kde::sweep_and_estimate(scaled_base_avg_times, KDE_POINTS, None, base_mean);
-    let (xs, ys, y_mean) =
-        kde::sweep_and_estimate(scaled_new_avg_times, KDE_POINTS, None, new_mean);
+    let (base_xs, base_ys, base_y_mean) = kde::sweep_and_estimate(
+        scaled_base_avg_times,
+        KDE_POINTS,
+        None,
+        base_mean,
+        plot_config.kde_kernel,
+        plot_config.kde_bandwidth,
+    );
+    let (xs, ys, y_mean) = kde::sweep_and_estimate(
+        scaled_new_avg_times,
+        KDE_POINTS,
+        None,
+        new_mean,
+        plot_config.kde_kernel,
+        plot_config.kde_bandwidth,
+    );
 
     let zeros = iter::repeat(0);
 
@@ -367,7 +405,13 @@ pub(crate) fn pdf_comparison(
     comparison: &ComparisonData,
     size: Option<Size>,
 ) -> Child {
-    let mut figure = pdf_comparison_figure(formatter, measurements, comparison, size);
+    let mut figure = pdf_comparison_figure(
+        formatter,
+        measurements,
+        comparison,
+        &context.plot_config,
+        size,
+    );
     figure.set(Title(gnuplot_escape(id.as_title())));
     let path = context.report_path(id, "both/pdf.svg");
     debug_script(&path, &figure);
@@ -382,7 +426,13 @@ pub(crate) fn pdf_comparison_small(
     comparison: &ComparisonData,
     size: Option<Size>,
 ) -> Child {
-    let mut figure = pdf_comparison_figure(formatter, measurements, comparison, size);
+    let mut figure = pdf_comparison_figure(
+        formatter,
+        measurements,
+        comparison,
+        &context.plot_config,
+        size,
+    );
     figure.configure(Key, |k| k.hide());
     let path = context.report_path(id, "relative_pdf_small.svg");
     debug_script(&path, &figure);
@@ -1,5 +1,6 @@
 use super::{debug_script, gnuplot_escape};
 use super::{DARK_BLUE, DEFAULT_FONT, KDE_POINTS, LINEWIDTH, POINT_SIZE, SIZE};
+use crate::complexity::ComplexityFit;
 use crate::kde;
 use crate::measurement::ValueFormatter;
 use crate::report::{BenchmarkId, ValueType};
@@ -115,6 +116,26 @@ pub fn line_comparison(
                 .set(COMPARISON_COLORS[i % NUM_COLORS])
         });
 
+        if let Some(fit) = ComplexityFit::fit(&xs, &ys) {
+            let (fit_xs, fit_ys) = fit.curve(&xs);
+            let label = match key {
+                Some(name) => format!("{}: {}", name, fit.label()),
+                None => fit.label(),
+            };
+            f.plot(
+                Lines {
+                    x: &fit_xs,
+                    y: &fit_ys,
+                },
+                |c| {
+                    c.set(Label(gnuplot_escape(&label)))
+                        .set(LINEWIDTH)
+                        .set(LineType::Dash)
+                        .set(COMPARISON_COLORS[i % NUM_COLORS])
+                },
+            );
+        }
+
         i += 1;
     }
 
@@ -11,8 +11,9 @@ pub(crate) fn t_test(
     comparison: &ComparisonData,
     size: Option<Size>,
 ) -> Child {
-    let t = comparison.t_value;
-    let (xs, ys) = kde::sweep(&comparison.t_distribution, KDE_POINTS, None);
+    let (t, t_distribution) = comparison.t_test.as_ref().expect("The t-test was not run");
+    let t = *t;
+    let (xs, ys) = kde::sweep(t_distribution, KDE_POINTS, None);
     let zero = iter::repeat(0);
 
     let mut figure = Figure::new();
@@ -80,6 +80,8 @@ pub(crate) trait Plotter {
 
     fn iteration_times(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>);
 
+    fn iteration_order(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>);
+
     fn abs_distributions(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>);
 
     fn rel_distributions(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>);
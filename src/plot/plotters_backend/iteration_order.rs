@@ -0,0 +1,82 @@
+use super::*;
+
+use std::path::Path;
+
+pub(crate) fn iteration_order_figure(
+    title: &str,
+    path: &Path,
+    formatter: &dyn ValueFormatter,
+    measurements: &MeasurementData<'_>,
+    size: Option<(u32, u32)>,
+) {
+    let data = &measurements.avg_times;
+    let max_avg_time = data.max();
+    let mut scaled_y: Vec<_> = data.iter().map(|(f, _)| f).collect();
+    let unit = formatter.scale_values(max_avg_time, &mut scaled_y);
+    let scaled_y = Sample::new(&scaled_y);
+
+    let size = size.unwrap_or(SIZE);
+    let root_area = SVGBackend::new(path, size).into_drawing_area();
+
+    let x_range = (1.0)..(data.len() as f64);
+    let y_range = plotters::data::fitting_range(scaled_y.iter());
+
+    let mut chart = ChartBuilder::on(&root_area)
+        .caption(title, (DEFAULT_FONT, 20))
+        .margin((5).percent())
+        .set_label_area_size(LabelAreaPosition::Left, (5).percent_width().min(60))
+        .set_label_area_size(LabelAreaPosition::Bottom, (5).percent_height().min(40))
+        .build_cartesian_2d(x_range, y_range)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc("Sample (in the order measured)")
+        .y_desc(format!("Average Iteration Time ({})", unit))
+        .x_label_formatter(&|x| pretty_print_float(*x, true))
+        .light_line_style(TRANSPARENT)
+        .draw()
+        .unwrap();
+
+    chart
+        .draw_series(LineSeries::new(
+            (1..=data.len())
+                .zip(scaled_y.iter())
+                .map(|(x, y)| (x as f64, *y)),
+            DARK_BLUE.mix(0.5),
+        ))
+        .unwrap();
+
+    chart
+        .draw_series(
+            (1..=data.len())
+                .zip(scaled_y.iter())
+                .map(|(x, y)| Circle::new((x as f64, *y), POINT_SIZE, DARK_BLUE.filled())),
+        )
+        .unwrap()
+        .label("Sample")
+        .legend(|(x, y)| Circle::new((x + 10, y), POINT_SIZE, DARK_BLUE.filled()));
+
+    // Show the means on either side of a shift in the measurements
+    if let Some(changepoint) = &measurements.order.changepoint {
+        let mut means = [changepoint.mean_before, changepoint.mean_after];
+        let _ = formatter.scale_values(max_avg_time, &mut means);
+        let [before, after] = means;
+        let shift = changepoint.index as f64 + 0.5;
+
+        chart
+            .draw_series(vec![
+                PathElement::new(vec![(1.0, before), (shift, before)], DARK_RED),
+                PathElement::new(vec![(shift, after), (data.len() as f64, after)], DARK_RED),
+            ])
+            .unwrap()
+            .label("Mean before and after the shift")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], DARK_RED));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .draw()
+        .unwrap();
+}
@@ -16,8 +16,10 @@ static POINT_SIZE: u32 = 3;
 const DARK_BLUE: RGBColor = RGBColor(31, 120, 180);
 const DARK_ORANGE: RGBColor = RGBColor(255, 127, 0);
 const DARK_RED: RGBColor = RGBColor(227, 26, 28);
+const DARK_GREEN: RGBColor = RGBColor(51, 160, 44);
 
 mod distributions;
+mod iteration_order;
 mod iteration_times;
 mod pdf;
 mod regression;
@@ -54,6 +56,7 @@ impl Plotter for PlottersBackend {
                 data.formatter,
                 data.measurements,
                 cmp,
+                &ctx.context.plot_config,
                 convert_size(ctx.size),
             );
             return;
@@ -120,6 +123,18 @@ impl Plotter for PlottersBackend {
         }
     }
 
+    fn iteration_order(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>) {
+        iteration_order::iteration_order_figure(
+            ctx.id.as_title(),
+            ctx.context
+                .report_path(ctx.id, "iteration_order.svg")
+                .as_path(),
+            data.formatter,
+            data.measurements,
+            convert_size(ctx.size),
+        );
+    }
+
     fn iteration_times(&mut self, ctx: PlotContext<'_>, data: PlotData<'_>) {
         let (title, path) = match (data.comparison.is_some(), ctx.is_thumbnail) {
             (true, true) => (
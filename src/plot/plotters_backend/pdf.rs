@@ -1,5 +1,6 @@
 use super::*;
 use crate::report::ReportContext;
+use crate::PlotConfiguration;
 use plotters::data;
 use std::path::Path;
 
@@ -9,6 +10,7 @@ pub(crate) fn pdf_comparison_figure(
     formatter: &dyn ValueFormatter,
     measurements: &MeasurementData<'_>,
     comparison: &ComparisonData,
+    plot_config: &PlotConfiguration,
     size: Option<(u32, u32)>,
 ) {
     let base_avg_times = Sample::new(&comparison.base_avg_times);
@@ -27,10 +29,22 @@ pub(crate) fn pdf_comparison_figure(
     let base_mean = scaled_base_avg_times.mean();
     let new_mean = scaled_new_avg_times.mean();
 
-    let (base_xs, base_ys, base_y_mean) =
-        kde::sweep_and_estimate(scaled_base_avg_times, KDE_POINTS, None, base_mean);
-    let (xs, ys, y_mean) =
-        kde::sweep_and_estimate(scaled_new_avg_times, KDE_POINTS, None, new_mean);
+    let (base_xs, base_ys, base_y_mean) = kde::sweep_and_estimate(
+        scaled_base_avg_times,
+        KDE_POINTS,
+        None,
+        base_mean,
+        plot_config.kde_kernel,
+        plot_config.kde_bandwidth,
+    );
+    let (xs, ys, y_mean) = kde::sweep_and_estimate(
+        scaled_new_avg_times,
+        KDE_POINTS,
+        None,
+        new_mean,
+        plot_config.kde_kernel,
+        plot_config.kde_bandwidth,
+    );
 
     let x_range = data::fitting_range(base_xs.iter().chain(xs.iter()));
     let y_range = data::fitting_range(base_ys.iter().chain(ys.iter()));
@@ -121,7 +135,14 @@ pub(crate) fn pdf_small(
     let scaled_avg_times = Sample::new(&scaled_avg_times);
     let mean = scaled_avg_times.mean();
 
-    let (xs, ys, mean_y) = kde::sweep_and_estimate(scaled_avg_times, KDE_POINTS, None, mean);
+    let (xs, ys, mean_y) = kde::sweep_and_estimate(
+        scaled_avg_times,
+        KDE_POINTS,
+        None,
+        mean,
+        context.plot_config.kde_kernel,
+        context.plot_config.kde_bandwidth,
+    );
     let xs_ = Sample::new(&xs);
     let ys_ = Sample::new(&ys);
 
@@ -195,7 +216,13 @@ pub(crate) fn pdf(
         format!("Iterations (x 10^{})", exponent)
     };
 
-    let (xs, ys) = kde::sweep(scaled_avg_times, KDE_POINTS, None);
+    let (xs, ys) = kde::sweep_with(
+        scaled_avg_times,
+        KDE_POINTS,
+        None,
+        context.plot_config.kde_kernel,
+        context.plot_config.kde_bandwidth,
+    );
     let (lost, lomt, himt, hist) = avg_times.fences();
     let mut fences = [lost, lomt, himt, hist];
     let _ = formatter.scale_values(typical, &mut fences);
@@ -260,13 +287,34 @@ pub(crate) fn pdf(
         .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], DARK_BLUE));
 
     chart
-        .draw_series(vec![
-            PathElement::new(vec![(lomt, 0.0), (lomt, max_iters)], DARK_ORANGE),
-            PathElement::new(vec![(himt, 0.0), (himt, max_iters)], DARK_ORANGE),
-            PathElement::new(vec![(lost, 0.0), (lost, max_iters)], DARK_RED),
-            PathElement::new(vec![(hist, 0.0), (hist, max_iters)], DARK_RED),
-        ])
+        .draw_series(
+            [
+                (lomt, DARK_ORANGE),
+                (himt, DARK_ORANGE),
+                (lost, DARK_RED),
+                (hist, DARK_RED),
+            ]
+            .into_iter()
+            // Fences are infinite if outlier detection is disabled
+            .filter(|(fence, _)| fence.is_finite())
+            .map(|(fence, color)| PathElement::new(vec![(fence, 0.0), (fence, max_iters)], color)),
+        )
         .unwrap();
+
+    // Mark the modes of a multimodal distribution
+    let mut modes = measurements.modes.clone();
+    let _ = formatter.scale_values(typical, &mut modes);
+    if modes.len() > 1 {
+        chart
+            .draw_series(
+                modes.iter().map(|&mode| {
+                    PathElement::new(vec![(mode, 0.0), (mode, max_iters)], DARK_GREEN)
+                }),
+            )
+            .unwrap()
+            .label("Modes")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], DARK_GREEN));
+    }
     use crate::stats::univariate::outliers::tukey::Label;
 
     let mut draw_data_point_series =
@@ -1,4 +1,5 @@
 use super::*;
+use crate::complexity::ComplexityFit;
 use crate::AxisScale;
 use itertools::Itertools;
 use plotters::coord::{
@@ -34,6 +35,21 @@ pub fn line_comparison(
         plotters::data::fitting_range(series_data.iter().flat_map(|(_, xs, _)| xs.iter()));
     let y_range =
         plotters::data::fitting_range(series_data.iter().flat_map(|(_, _, ys)| ys.iter()));
+    // The fitted curves are cut off where they leave the range of the measurements
+    let fits = series_data
+        .iter()
+        .map(|(_, xs, ys)| {
+            ComplexityFit::fit(xs, ys).map(|fit| {
+                let (fit_xs, fit_ys) = fit.curve(xs);
+                let points = fit_xs
+                    .into_iter()
+                    .zip(fit_ys)
+                    .filter(|(_, y)| y_range.contains(y))
+                    .collect::<Vec<_>>();
+                (fit, points)
+            })
+        })
+        .collect::<Vec<_>>();
     let root_area = SVGBackend::new(&path, SIZE)
         .into_drawing_area()
         .titled(&format!("{}: Comparison", title), (DEFAULT_FONT, 20))
@@ -41,7 +57,15 @@ pub fn line_comparison(
 
     match axis_scale {
         AxisScale::Linear => {
-            draw_line_comarision_figure(root_area, unit, x_range, y_range, value_type, series_data);
+            draw_line_comarision_figure(
+                root_area,
+                unit,
+                x_range,
+                y_range,
+                value_type,
+                series_data,
+                fits,
+            );
         }
         AxisScale::Logarithmic => draw_line_comarision_figure(
             root_area,
@@ -50,10 +74,12 @@ pub fn line_comparison(
             y_range.log_scale(),
             value_type,
             series_data,
+            fits,
         ),
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn draw_line_comarision_figure<XR: AsRangedCoord<Value = f64>, YR: AsRangedCoord<Value = f64>>(
     root_area: DrawingArea<SVGBackend, Shift>,
     y_unit: &str,
@@ -61,6 +87,7 @@ fn draw_line_comarision_figure<XR: AsRangedCoord<Value = f64>, YR: AsRangedCoord
     y_range: YR,
     value_type: ValueType,
     data: Vec<(Option<&String>, Vec<f64>, Vec<f64>)>,
+    fits: Vec<Option<(ComplexityFit, Vec<(f64, f64)>)>>,
 ) where
     XR::CoordDescType: PlottersValueFormatter<f64>,
     YR::CoordDescType: PlottersValueFormatter<f64>,
@@ -87,7 +114,27 @@ fn draw_line_comarision_figure<XR: AsRangedCoord<Value = f64>, YR: AsRangedCoord
         .draw()
         .unwrap();
 
-    for (id, (name, xs, ys)) in (0..).zip(data) {
+    for (id, ((name, xs, ys), fit)) in (0..).zip(data.into_iter().zip(fits)) {
+        if let Some((fit, points)) = fit {
+            let label = match name {
+                Some(name) => format!("{}: {}", name, fit.label()),
+                None => fit.label(),
+            };
+            chart
+                .draw_series(LineSeries::new(
+                    points,
+                    COMPARISON_COLORS[id % NUM_COLORS].mix(0.5).stroke_width(2),
+                ))
+                .unwrap()
+                .label(label)
+                .legend(move |(x, y)| {
+                    PathElement::new(
+                        vec![(x, y), (x + 20, y)],
+                        COMPARISON_COLORS[id % NUM_COLORS].mix(0.5).stroke_width(2),
+                    )
+                });
+        }
+
         let series = chart
             .draw_series(
                 LineSeries::new(
@@ -7,8 +7,9 @@ pub(crate) fn t_test(
     comparison: &ComparisonData,
     size: Option<(u32, u32)>,
 ) {
-    let t = comparison.t_value;
-    let (xs, ys) = kde::sweep(&comparison.t_distribution, KDE_POINTS, None);
+    let (t, t_distribution) = comparison.t_test.as_ref().expect("The t-test was not run");
+    let t = *t;
+    let (xs, ys) = kde::sweep(t_distribution, KDE_POINTS, None);
 
     let x_range = plotters::data::fitting_range(xs.iter());
     let mut y_range = plotters::data::fitting_range(ys.iter());
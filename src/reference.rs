@@ -0,0 +1,175 @@
+//! Performance relative to a reference function
+//!
+//! A benchmark group can name one of its functions as the reference. Every other benchmark of the
+//! group is then compared against the reference benchmark with the same parameter, and reported
+//! as a ratio of their mean times (eg. `2.30× faster than std`), bootstrapped to give a confidence
+//! interval on the ratio. The ratios computed during a run are also recorded here, so they can be
+//! repeated in the final summary.
+
+use std::sync::Mutex;
+
+use crate::estimate::{build_estimate, Estimate};
+use crate::report::BenchmarkId;
+use crate::stats::univariate::{self, Sample};
+
+/// The performance of a benchmark relative to the reference benchmark with the same parameter
+#[derive(Debug, Clone)]
+pub(crate) struct RelativePerformance {
+    pub id: BenchmarkId,
+    pub reference: BenchmarkId,
+    /// The mean time of the reference divided by the mean time of the benchmark, so a ratio
+    /// above one means the benchmark is faster than the reference.
+    pub ratio: Estimate,
+}
+impl RelativePerformance {
+    /// The ratio, inverted if the benchmark is slower than the reference
+    fn speedup(&self) -> (f64, f64, f64) {
+        let ratio = &self.ratio;
+        let interval = &ratio.confidence_interval;
+        if self.is_faster() {
+            (
+                interval.lower_bound,
+                ratio.point_estimate,
+                interval.upper_bound,
+            )
+        } else {
+            (
+                interval.upper_bound.recip(),
+                ratio.point_estimate.recip(),
+                interval.lower_bound.recip(),
+            )
+        }
+    }
+
+    /// Whether the benchmark is faster than the reference, going by the point estimate
+    pub fn is_faster(&self) -> bool {
+        self.ratio.point_estimate >= 1.0
+    }
+
+    /// Describes the ratio, eg. `2.30× faster than std`. Ratios below one are inverted and
+    /// described as slower.
+    pub fn comparison(&self) -> String {
+        let reference = self
+            .reference
+            .function_id
+            .as_deref()
+            .unwrap_or(&self.reference.group_id);
+        let (_, point, _) = self.speedup();
+        let direction = if self.is_faster() { "faster" } else { "slower" };
+        format!("{:.2}× {} than {}", point, direction, reference)
+    }
+
+    /// The confidence interval of the ratio as it is described, eg. `[2.10× 2.52×]`
+    pub fn interval(&self) -> String {
+        let (lower, _, upper) = self.speedup();
+        format!("[{:.2}× {:.2}×]", lower, upper)
+    }
+}
+
+/// Compares every benchmark of a group against the reference benchmark with the same parameter,
+/// given the average iteration times of each benchmark. Benchmarks without a matching reference
+/// benchmark are skipped.
+pub(crate) fn compare(
+    reference: &str,
+    data: &[(&BenchmarkId, Vec<f64>)],
+    nresamples: usize,
+    confidence_level: f64,
+) -> Vec<RelativePerformance> {
+    let is_reference = |id: &BenchmarkId| id.function_id.as_deref() == Some(reference);
+
+    data.iter()
+        .filter(|&&(id, _)| !is_reference(id))
+        .filter_map(|&(id, ref times)| {
+            let (reference_id, reference_times) = data
+                .iter()
+                .find(|&&(other, _)| is_reference(other) && other.value_str == id.value_str)?;
+
+            let reference_times = Sample::new(reference_times);
+            let times = Sample::new(times);
+            let point = reference_times.mean() / times.mean();
+            let (distribution,) =
+                univariate::bootstrap(reference_times, times, nresamples, |reference, times| {
+                    (reference.mean() / times.mean(),)
+                });
+
+            Some(RelativePerformance {
+                id: id.clone(),
+                reference: (*reference_id).clone(),
+                ratio: build_estimate(point, &distribution, None, confidence_level),
+            })
+        })
+        .collect()
+}
+
+static RECORDED: Mutex<Vec<RelativePerformance>> = Mutex::new(Vec::new());
+
+/// Records the relative performance of the benchmarks of a group for the final summary.
+pub(crate) fn record(relative: &[RelativePerformance]) {
+    RECORDED.lock().unwrap().extend_from_slice(relative);
+}
+
+/// Takes the relative performance recorded so far.
+pub(crate) fn take() -> Vec<RelativePerformance> {
+    std::mem::take(&mut *RECORDED.lock().unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::compare;
+    use crate::report::BenchmarkId;
+
+    fn id(function: &str, value: &str) -> BenchmarkId {
+        BenchmarkId::new(
+            "group".to_owned(),
+            Some(function.to_owned()),
+            Some(value.to_owned()),
+            None,
+        )
+    }
+
+    #[test]
+    fn compares_against_matching_reference() {
+        let (std_1, std_2, fast_1, slow_2, other_3) = (
+            id("std", "1"),
+            id("std", "2"),
+            id("fast", "1"),
+            id("slow", "2"),
+            id("other", "3"),
+        );
+        let times = |mean: f64| {
+            (0..20)
+                .map(|i| mean * (1.0 + (i % 5) as f64 * 0.01))
+                .collect()
+        };
+        let data = vec![
+            (&std_1, times(10.0)),
+            (&std_2, times(10.0)),
+            (&fast_1, times(4.0)),
+            (&slow_2, times(20.0)),
+            (&other_3, times(1.0)),
+        ];
+
+        let relative = compare("std", &data, 1000, 0.95);
+        assert_eq!(2, relative.len());
+
+        assert_eq!(fast_1, relative[0].id);
+        assert_eq!(std_1, relative[0].reference);
+        let ratio = &relative[0].ratio;
+        assert!((ratio.point_estimate - 2.5).abs() < 0.01);
+        assert!(ratio.confidence_interval.lower_bound <= ratio.point_estimate);
+        assert!(ratio.confidence_interval.upper_bound >= ratio.point_estimate);
+        assert_eq!("2.50× faster than std", relative[0].comparison());
+
+        assert_eq!(slow_2, relative[1].id);
+        assert_eq!(std_2, relative[1].reference);
+        assert_eq!("2.00× slower than std", relative[1].comparison());
+        let (lower, upper) = (
+            relative[1].ratio.confidence_interval.lower_bound,
+            relative[1].ratio.confidence_interval.upper_bound,
+        );
+        assert_eq!(
+            format!("[{:.2}× {:.2}×]", upper.recip(), lower.recip()),
+            relative[1].interval()
+        );
+    }
+}
@@ -1,15 +1,26 @@
 #[cfg(feature = "csv_output")]
 use crate::csv_report::FileCsvReport;
 use crate::stats::bivariate::regression::Slope;
+use crate::stats::univariate::drift::Changepoint;
 use crate::stats::univariate::outliers::tukey::LabeledSample;
 use crate::{html::Html, stats::bivariate::Data};
 
-use crate::estimate::{ChangeDistributions, ChangeEstimates, Distributions, Estimate, Estimates};
+use crate::complexity;
+use crate::correction::CorrectedComparisons;
+use crate::environment::EnvironmentReport;
+use crate::estimate::{
+    ChangeDistributions, ChangeEstimates, Distributions, Equivalence, Estimate, Estimates,
+};
 use crate::format;
 use crate::measurement::ValueFormatter;
+use crate::noise::LearnedThreshold;
+use crate::reference::RelativePerformance;
 use crate::stats::univariate::Sample;
 use crate::stats::Distribution;
-use crate::{PlotConfiguration, Throughput};
+use crate::{
+    fs, ComparisonReport, ComparisonTest, MultipleComparisonCorrection, PlotConfiguration,
+    SavedSample, Throughput, TrackedStatistic,
+};
 use anes::{Attribute, ClearLine, Color, ResetAttributes, SetAttribute, SetForegroundColor};
 use serde::{Deserialize, Serialize};
 use std::cmp;
@@ -18,24 +29,152 @@ use std::fmt;
 use std::io::stderr;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const MAX_DIRECTORY_NAME_LEN: usize = 64;
 const MAX_TITLE_LEN: usize = 100;
 
+// Formats a signed shift in the measured value, eg. "+1.2345 ns"
+pub(crate) fn format_shift(formatter: &dyn ValueFormatter, shift: f64) -> String {
+    format!(
+        "{}{}",
+        if shift < 0.0 { "-" } else { "+" },
+        formatter.format_value(shift.abs()).trim_start()
+    )
+}
+
+// Labels the p-value with the name of the hypothesis test, unless it's the default t-test
+pub(crate) fn comparison_test_label(test: ComparisonTest) -> String {
+    match test {
+        ComparisonTest::TTest => String::new(),
+        _ => format!("{} ", test.name()),
+    }
+}
+
+// Describes the evidence for a change, as configured: the p-value of the hypothesis test, the
+// probability of a change beyond the noise threshold in the direction of the observed change, or
+// both. eg. "p = 0.00 < 0.05, 93.1% likely a regression > 2.00%". The p-value of a statistic
+// other than the mean comes from its bootstrap distribution, eg. "p99 bootstrap p = 0.01 < 0.05".
+pub(crate) fn change_evidence(comp: &ComparisonData) -> String {
+    let label = match comp.test {
+        Some(test) => comparison_test_label(test),
+        None => format!("{} bootstrap ", comp.statistic),
+    };
+    let p_value = format!(
+        "{}{}p = {:.2} {} {:.2}",
+        label,
+        if comp.correction.is_some() {
+            "unadjusted "
+        } else {
+            ""
+        },
+        comp.p_value,
+        if comp.p_value < comp.significance_threshold {
+            "<"
+        } else {
+            ">"
+        },
+        comp.significance_threshold
+    );
+    let probability = comp.relative_estimates.probabilities.as_ref().map(|p| {
+        let (probability, change) = if comp.verdict_estimate().point_estimate < 0.0 {
+            (p.improvement, "an improvement")
+        } else {
+            (p.regression, "a regression")
+        };
+        format!(
+            "{:.1}% likely {} > {:.2}%",
+            probability * 1e2,
+            change,
+            p.noise_threshold * 1e2
+        )
+    });
+
+    match (comp.report, probability) {
+        (ComparisonReport::Probability, Some(probability)) => probability,
+        (ComparisonReport::Both, Some(probability)) => format!("{}, {}", p_value, probability),
+        _ => p_value,
+    }
+}
+
 pub(crate) struct ComparisonData {
+    /// The test of the change in the mean, or `None` if another statistic decides the verdict
+    pub test: Option<ComparisonTest>,
+    pub report: ComparisonReport,
+    /// The statistic whose change decides the verdict
+    pub statistic: TrackedStatistic,
     pub p_value: f64,
-    pub t_distribution: Distribution<f64>,
-    pub t_value: f64,
+    /// The correction the verdict waits for, if the comparisons of the run are corrected together
+    /// at its end
+    pub correction: Option<MultipleComparisonCorrection>,
+    /// The t statistic and its bootstrapped distribution, if the t-test was run
+    pub t_test: Option<(f64, Distribution<f64>)>,
+    /// The verdict of the equivalence test, if a margin was given
+    pub equivalence: Option<Equivalence>,
     pub relative_estimates: ChangeEstimates,
     pub relative_distributions: ChangeDistributions,
     pub significance_threshold: f64,
     pub noise_threshold: f64,
+    /// The noise threshold learned from the history of the benchmark, if it was learned
+    pub learned_noise: Option<LearnedThreshold>,
     pub base_iter_counts: Vec<f64>,
     pub base_sample_times: Vec<f64>,
     pub base_avg_times: Vec<f64>,
     pub base_estimates: Estimates,
 }
 
+impl ComparisonData {
+    /// The estimate of the change in the tracked statistic
+    pub fn verdict_estimate(&self) -> &Estimate {
+        self.relative_estimates
+            .try_get(self.statistic.into())
+            .unwrap_or(&self.relative_estimates.mean)
+    }
+
+    /// Whether the change in the tracked statistic is statistically significant. The change in the
+    /// mean is tested with the comparison test, and the change in any other statistic with its
+    /// bootstrap distribution.
+    pub fn is_significant(&self) -> bool {
+        self.p_value < self.significance_threshold
+    }
+}
+
+/// The result of comparing two interleaved benchmarks using a paired test.
+pub(crate) struct PairedComparisonData {
+    pub p_value: f64,
+    pub t_value: f64,
+    pub relative_estimates: ChangeEstimates,
+    pub significance_threshold: f64,
+    pub noise_threshold: f64,
+}
+
+/// Diagnostics of the order in which the measurements were taken.
+pub(crate) struct OrderDiagnostics {
+    pub autocorrelation: f64,
+    pub effective_sample_size: f64,
+    /// Whether successive measurements are correlated enough to warn about
+    pub correlated: bool,
+    /// A significant shift in the measurements during the run, if any
+    pub changepoint: Option<Changepoint>,
+}
+
+/// How large a change in the mean the benchmark can detect, and what it would take to detect a
+/// change of the target size.
+pub(crate) struct PowerAnalysis {
+    /// The probability of detecting a change of the minimum detectable size
+    pub power: f64,
+    /// The smallest relative change that is detected with the given power
+    pub minimum_detectable_effect: f64,
+    /// The relative change planned for
+    pub target_effect: f64,
+    /// The number of runs the sample was taken in
+    pub runs: usize,
+    /// The number of samples per run, and the measurement time per run, needed to detect the
+    /// target effect with the given power, or `None` if the variance between runs is too large
+    /// for any number of samples per run to detect it
+    pub required: Option<(usize, Duration)>,
+}
+
 pub(crate) struct MeasurementData<'a> {
     pub data: Data<'a, f64, f64>,
     pub avg_times: LabeledSample<'a, f64>,
@@ -43,6 +182,9 @@ pub(crate) struct MeasurementData<'a> {
     pub distributions: Distributions,
     pub comparison: Option<ComparisonData>,
     pub throughput: Option<Throughput>,
+    pub modes: Vec<f64>,
+    pub order: OrderDiagnostics,
+    pub power: PowerAnalysis,
 }
 impl<'a> MeasurementData<'a> {
     pub fn iter_counts(&self) -> &Sample<f64> {
@@ -257,6 +399,7 @@ impl fmt::Debug for BenchmarkId {
     }
 }
 
+#[derive(Clone)]
 pub struct ReportContext {
     pub output_directory: PathBuf,
     pub plot_config: PlotConfiguration,
@@ -269,6 +412,32 @@ impl ReportContext {
         path.push(file_name);
         path
     }
+
+    /// Loads the saved sample of each benchmark, as average times per iteration.
+    pub(crate) fn load_summary_data<'a>(
+        &self,
+        all_ids: &[&'a BenchmarkId],
+    ) -> Vec<(&'a BenchmarkId, Vec<f64>)> {
+        all_ids
+            .iter()
+            .filter_map(|id| {
+                let entry = self
+                    .output_directory
+                    .join(id.as_directory_name())
+                    .join("new");
+
+                let SavedSample { iters, times, .. } =
+                    try_else_return!(fs::load(&entry.join("sample.json")), || None);
+                let avg_times = iters
+                    .into_iter()
+                    .zip(times)
+                    .map(|(iters, time)| time / iters)
+                    .collect::<Vec<_>>();
+
+                Some((*id, avg_times))
+            })
+            .collect::<Vec<_>>()
+    }
 }
 
 pub(crate) trait Report {
@@ -301,11 +470,23 @@ pub(crate) trait Report {
         &self,
         _context: &ReportContext,
         _all_ids: &[BenchmarkId],
+        _relative: &[RelativePerformance],
         _formatter: &dyn ValueFormatter,
     ) {
     }
     fn final_summary(&self, _context: &ReportContext) {}
     fn group_separator(&self) {}
+    fn environment(&self, _environment: &EnvironmentReport) {}
+    fn paired_comparison(
+        &self,
+        _id: &BenchmarkId,
+        _reference_id: &BenchmarkId,
+        _context: &ReportContext,
+        _comparison: &PairedComparisonData,
+    ) {
+    }
+    fn multiple_comparisons(&self, _context: &ReportContext, _corrected: &CorrectedComparisons) {}
+    fn relative_performance(&self, _context: &ReportContext, _relative: &[RelativePerformance]) {}
 }
 
 pub(crate) struct Reports {
@@ -365,11 +546,25 @@ impl Report for Reports {
         &self,
         context: &ReportContext,
         all_ids: &[BenchmarkId],
+        relative: &[RelativePerformance],
         formatter: &dyn ValueFormatter
     ));
 
     reports_impl!(fn final_summary(&self, context: &ReportContext));
     reports_impl!(fn group_separator(&self, ));
+    reports_impl!(fn environment(&self, environment: &EnvironmentReport));
+    reports_impl!(
+    fn paired_comparison(
+        &self,
+        id: &BenchmarkId,
+        reference_id: &BenchmarkId,
+        context: &ReportContext,
+        comparison: &PairedComparisonData
+    ));
+    reports_impl!(
+    fn multiple_comparisons(&self, context: &ReportContext, corrected: &CorrectedComparisons));
+    reports_impl!(
+    fn relative_performance(&self, context: &ReportContext, relative: &[RelativePerformance]));
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -450,6 +645,96 @@ impl CliReport {
         }
     }
 
+    pub fn modes(&self, modes: &[f64], formatter: &dyn ValueFormatter) {
+        if modes.len() < 2 {
+            return;
+        }
+
+        let modes = modes
+            .iter()
+            .map(|&mode| formatter.format_value(mode).trim_start().to_owned())
+            .collect::<Vec<_>>();
+        println!(
+            "{}",
+            self.yellow(&format!(
+                "Warning: The measurements are multimodal, with modes at {}. The mean and slope \
+                may be misleading; see the PDF plot.",
+                modes.join(", ")
+            ))
+        );
+    }
+
+    pub fn order(&self, order: &OrderDiagnostics, sample_size: usize) {
+        if order.correlated {
+            println!(
+                "{}",
+                self.yellow(&format!(
+                    "Warning: Successive measurements are correlated (lag-1 autocorrelation of \
+                    {:.2}), so the {} measurements are only worth about {:.0} independent ones. \
+                    The confidence intervals may be too narrow.",
+                    order.autocorrelation, sample_size, order.effective_sample_size
+                ))
+            );
+        }
+        if let Some(changepoint) = &order.changepoint {
+            println!(
+                "{}",
+                self.yellow(&format!(
+                    "Warning: The measurements shifted by {} from sample {} of {}, which \
+                    suggests that the performance of the machine changed during the run (eg. \
+                    thermal throttling).",
+                    format::change(changepoint.mean_after / changepoint.mean_before - 1.0, true),
+                    changepoint.index + 1,
+                    sample_size
+                ))
+            );
+        }
+    }
+
+    // Describes the verdict of an equivalence test, eg. "Equivalent within ±2.00% (TOST p = 0.01
+    // < 0.05)."
+    fn equivalence(&self, equivalence: &Equivalence, significance_threshold: f64) -> String {
+        let margin = format!("±{:.2}%", equivalence.margin * 1e2);
+        if equivalence.equivalent {
+            format!(
+                "{} within {} (TOST p = {:.2} < {:.2}).",
+                self.green("Equivalent"),
+                margin,
+                equivalence.p_value,
+                significance_threshold
+            )
+        } else {
+            format!(
+                "Not shown to be equivalent within {} (TOST p = {:.2} > {:.2}).",
+                margin, equivalence.p_value, significance_threshold
+            )
+        }
+    }
+
+    // Prints the ratio of a benchmark to its reference, eg. "2.30× faster than std [2.10× 2.52×]",
+    // after the benchmark's title.
+    fn print_relative_performance(&self, relative: &RelativePerformance) {
+        let mut id = relative.id.as_title().to_owned();
+        if id.len() > 23 {
+            println!("{}", self.green(&id));
+            id.clear();
+        }
+        let id_len = id.len();
+
+        let comparison = if relative.is_faster() {
+            self.green(&self.bold(relative.comparison()))
+        } else {
+            self.red(&self.bold(relative.comparison()))
+        };
+        println!(
+            "{}{}{} {}",
+            self.green(&id),
+            " ".repeat(24 - id_len),
+            comparison,
+            self.faint(relative.interval())
+        );
+    }
+
     pub fn outliers(&self, sample: &LabeledSample<'_, f64>) {
         let (los, lom, _, him, his) = sample.count();
         let noutliers = los + lom + him + his;
@@ -597,11 +882,26 @@ impl Report for CliReport {
             );
         }
 
+        // The statistics estimated on request, each aligned with the time
+        for (statistic, estimate) in meas.absolute_estimates.extra_statistics() {
+            println!(
+                "{:>31} [{} {} {}]",
+                format!("{}:", statistic),
+                self.faint(formatter.format_value(estimate.confidence_interval.lower_bound)),
+                formatter.format_value(estimate.point_estimate),
+                self.faint(formatter.format_value(estimate.confidence_interval.upper_bound))
+            );
+        }
+
         if !matches!(self.verbosity, CliVerbosity::Quiet) {
             if let Some(ref comp) = meas.comparison {
-                let different_mean = comp.p_value < comp.significance_threshold;
-                let mean_est = &comp.relative_estimates.mean;
-                let point_estimate = mean_est.point_estimate;
+                let different = comp.is_significant();
+                let change_est = comp.verdict_estimate();
+                let change_label = match comp.statistic {
+                    TrackedStatistic::Mean => "change:".to_owned(),
+                    statistic => format!("{} change:", statistic),
+                };
+                let point_estimate = change_est.point_estimate;
                 let mut point_estimate_str = format::change(point_estimate, true);
                 // The change in throughput is related to the change in timing. Reducing the timing by
                 // 50% increases the throughput by 100%.
@@ -610,10 +910,12 @@ impl Report for CliReport {
                     format::change(to_thrpt_estimate(point_estimate), true);
                 let explanation_str: String;
 
-                if !different_mean {
+                if comp.correction.is_some() {
+                    explanation_str = "Verdict at the end of the run, after correction.".to_owned();
+                } else if !different {
                     explanation_str = "No change in performance detected.".to_owned();
                 } else {
-                    let comparison = compare_to_threshold(mean_est, comp.noise_threshold);
+                    let comparison = compare_to_threshold(change_est, comp.noise_threshold);
                     match comparison {
                         ComparisonResult::Improved => {
                             point_estimate_str = self.green(&self.bold(point_estimate_str));
@@ -635,62 +937,75 @@ impl Report for CliReport {
                 }
 
                 if meas.throughput.is_some() {
-                    println!("{}change:", " ".repeat(17));
+                    println!("{:>24}", change_label);
 
                     println!(
-                        "{}time:   [{} {} {}] (p = {:.2} {} {:.2})",
+                        "{}time:   [{} {} {}] ({})",
                         " ".repeat(24),
                         self.faint(format::change(
-                            mean_est.confidence_interval.lower_bound,
+                            change_est.confidence_interval.lower_bound,
                             true
                         )),
                         point_estimate_str,
                         self.faint(format::change(
-                            mean_est.confidence_interval.upper_bound,
+                            change_est.confidence_interval.upper_bound,
                             true
                         )),
-                        comp.p_value,
-                        if different_mean { "<" } else { ">" },
-                        comp.significance_threshold
+                        change_evidence(comp)
                     );
                     println!(
                         "{}thrpt:  [{} {} {}]",
                         " ".repeat(24),
                         self.faint(format::change(
-                            to_thrpt_estimate(mean_est.confidence_interval.upper_bound),
+                            to_thrpt_estimate(change_est.confidence_interval.upper_bound),
                             true
                         )),
                         thrpt_point_estimate_str,
                         self.faint(format::change(
-                            to_thrpt_estimate(mean_est.confidence_interval.lower_bound),
+                            to_thrpt_estimate(change_est.confidence_interval.lower_bound),
                             true
                         )),
                     );
                 } else {
                     println!(
-                        "{}change: [{} {} {}] (p = {:.2} {} {:.2})",
-                        " ".repeat(24),
+                        "{:>31} [{} {} {}] ({})",
+                        change_label,
                         self.faint(format::change(
-                            mean_est.confidence_interval.lower_bound,
+                            change_est.confidence_interval.lower_bound,
                             true
                         )),
                         point_estimate_str,
                         self.faint(format::change(
-                            mean_est.confidence_interval.upper_bound,
+                            change_est.confidence_interval.upper_bound,
                             true
                         )),
-                        comp.p_value,
-                        if different_mean { "<" } else { ">" },
-                        comp.significance_threshold
+                        change_evidence(comp)
                     );
                 }
 
                 println!("{}{}", " ".repeat(24), explanation_str);
+                if let Some(ref equivalence) = comp.equivalence {
+                    println!(
+                        "{}{}",
+                        " ".repeat(24),
+                        self.equivalence(equivalence, comp.significance_threshold)
+                    );
+                }
             }
         }
 
         if !matches!(self.verbosity, CliVerbosity::Quiet) {
             self.outliers(&meas.avg_times);
+            if let Some(excluded) = meas.absolute_estimates.excluded_outliers {
+                if excluded > 0 {
+                    println!(
+                        "  {} severe outliers were excluded from the estimates",
+                        excluded
+                    );
+                }
+            }
+            self.modes(&meas.modes, formatter);
+            self.order(&meas.order, meas.avg_times.len());
         }
 
         if matches!(self.verbosity, CliVerbosity::Verbose) {
@@ -713,6 +1028,18 @@ impl Report for CliReport {
                     Slope(slope_estimate.confidence_interval.upper_bound).r_squared(data),
                 );
             }
+            if let (Some(slope_estimate), Some(intercept_estimate)) = (
+                meas.absolute_estimates.slope_with_intercept.as_ref(),
+                meas.absolute_estimates.intercept.as_ref(),
+            ) {
+                println!(
+                    "{:<7}{} {:<15}{}",
+                    "affine",
+                    format_short_estimate(slope_estimate),
+                    "intercept",
+                    format_short_estimate(intercept_estimate),
+                );
+            }
             println!(
                 "{:<7}{} {:<15}{}",
                 "mean",
@@ -727,12 +1054,266 @@ impl Report for CliReport {
                 "med. abs. dev.",
                 format_short_estimate(&meas.absolute_estimates.median_abs_dev),
             );
+            println!(
+                "{:<7}{:<21} {:<15}{:.1}",
+                "order",
+                format!("autocorr. {:.3}", meas.order.autocorrelation),
+                "eff. sample",
+                meas.order.effective_sample_size,
+            );
+            if let Some(learned) = meas.comparison.as_ref().and_then(|comp| comp.learned_noise) {
+                println!(
+                    "{:<7}±{:.2}% learned from {} comparisons",
+                    "noise",
+                    learned.threshold * 1e2,
+                    learned.history,
+                );
+            }
+            let power = &meas.power;
+            let per_run = if power.runs > 1 { " per run" } else { "" };
+            println!(
+                "{:<7}{:<21} {:<15}{}",
+                "power",
+                format!(
+                    "detects ±{:.2}% ({:.0}%)",
+                    power.minimum_detectable_effect * 1e2,
+                    power.power * 1e2
+                ),
+                format!("for ±{:.2}%", power.target_effect * 1e2),
+                match power.required {
+                    Some((sample_size, time)) => format!(
+                        "{} samples in {}{}",
+                        sample_size,
+                        format::time(time.as_nanos() as f64),
+                        per_run
+                    ),
+                    None => "more runs needed".to_owned(),
+                },
+            );
+            if let Some(effect_sizes) = meas
+                .comparison
+                .as_ref()
+                .and_then(|comp| comp.relative_estimates.effect_sizes.as_ref())
+            {
+                println!(
+                    "{:<7}Cohen's d = {:+.3}, shift = {}, P(new > base) = {:.3}",
+                    "effect",
+                    effect_sizes.cohens_d,
+                    format_shift(formatter, effect_sizes.hodges_lehmann),
+                    effect_sizes.probability_of_superiority,
+                );
+            }
+        }
+    }
+
+    fn summarize(
+        &self,
+        context: &ReportContext,
+        all_ids: &[BenchmarkId],
+        relative: &[RelativePerformance],
+        formatter: &dyn ValueFormatter,
+    ) {
+        if matches!(self.verbosity, CliVerbosity::Quiet) {
+            return;
+        }
+
+        for relative in relative {
+            self.print_relative_performance(relative);
+        }
+
+        if !all_ids.iter().all(|id| id.as_number().is_some()) {
+            return;
+        }
+
+        let all_ids = all_ids
+            .iter()
+            .filter(|id| fs::is_dir(&context.output_directory.join(id.as_directory_name())))
+            .collect::<Vec<_>>();
+        if all_ids.is_empty() {
+            return;
+        }
+        let data = context.load_summary_data(&all_ids);
+        let all_curves = data.iter().collect::<Vec<_>>();
+
+        let group_id = &all_ids[0].group_id;
+        for (function_id, fit) in complexity::fit_functions(&all_curves) {
+            let title = match function_id {
+                Some(function_id) => format!("{}/{}", group_id, function_id),
+                None => group_id.clone(),
+            };
+            println!(
+                "{:<24}complexity: {}",
+                title,
+                fit.describe(|value| formatter.format_value(value))
+            );
         }
     }
 
     fn group_separator(&self) {
         println!();
     }
+
+    fn environment(&self, environment: &EnvironmentReport) {
+        if matches!(self.verbosity, CliVerbosity::Quiet) {
+            return;
+        }
+        for warning in environment.warnings() {
+            println!("{}", self.yellow(&format!("Warning: {}", warning)));
+        }
+    }
+
+    fn paired_comparison(
+        &self,
+        id: &BenchmarkId,
+        reference_id: &BenchmarkId,
+        _: &ReportContext,
+        comp: &PairedComparisonData,
+    ) {
+        if matches!(self.verbosity, CliVerbosity::Quiet) {
+            return;
+        }
+
+        let different_mean = comp.p_value < comp.significance_threshold;
+        let mean_est = &comp.relative_estimates.mean;
+        let mut point_estimate_str = format::change(mean_est.point_estimate, true);
+        let explanation_str: String;
+
+        if !different_mean {
+            explanation_str = "No difference in performance detected.".to_owned();
+        } else {
+            match compare_to_threshold(mean_est, comp.noise_threshold) {
+                ComparisonResult::Improved => {
+                    point_estimate_str = self.green(&self.bold(point_estimate_str));
+                    explanation_str = format!("{} is {}.", id.as_title(), self.green("faster"));
+                }
+                ComparisonResult::Regressed => {
+                    point_estimate_str = self.red(&self.bold(point_estimate_str));
+                    explanation_str = format!("{} is {}.", id.as_title(), self.red("slower"));
+                }
+                ComparisonResult::NonSignificant => {
+                    explanation_str = "Difference within noise threshold.".to_owned();
+                }
+            }
+        }
+
+        println!(
+            "{} vs. {} (paired):",
+            self.green(id.as_title()),
+            reference_id.as_title()
+        );
+        println!(
+            "{}change: [{} {} {}] (p = {:.2} {} {:.2})",
+            " ".repeat(24),
+            self.faint(format::change(
+                mean_est.confidence_interval.lower_bound,
+                true
+            )),
+            point_estimate_str,
+            self.faint(format::change(
+                mean_est.confidence_interval.upper_bound,
+                true
+            )),
+            comp.p_value,
+            if different_mean { "<" } else { ">" },
+            comp.significance_threshold
+        );
+        println!("{}{}", " ".repeat(24), explanation_str);
+    }
+
+    fn multiple_comparisons(&self, _context: &ReportContext, corrected: &CorrectedComparisons) {
+        if matches!(self.verbosity, CliVerbosity::Quiet) {
+            return;
+        }
+
+        let significant_before = corrected
+            .comparisons
+            .iter()
+            .filter(|comp| comp.p_value < comp.significance_threshold)
+            .count();
+        let significant_after = corrected
+            .comparisons
+            .iter()
+            .zip(&corrected.adjusted_p_values)
+            .filter(|(comp, &adjusted)| adjusted < comp.significance_threshold)
+            .count();
+
+        println!(
+            "\nMultiple comparison correction ({}) across the {} comparisons of the run:",
+            corrected.method.name(),
+            corrected.comparisons.len(),
+        );
+        println!(
+            "{} significant before correction, {} after.",
+            significant_before, significant_after
+        );
+
+        for (comp, &adjusted) in corrected
+            .comparisons
+            .iter()
+            .zip(&corrected.adjusted_p_values)
+            .filter(|(comp, _)| comp.p_value < comp.significance_threshold)
+        {
+            let changed = adjusted < comp.significance_threshold;
+            let mut point_estimate_str = format::change(comp.change.point_estimate, true);
+            let explanation_str: String;
+
+            if !changed {
+                explanation_str = "No change in performance detected after correction.".to_owned();
+            } else {
+                match compare_to_threshold(&comp.change, comp.noise_threshold) {
+                    ComparisonResult::Improved => {
+                        point_estimate_str = self.green(&self.bold(point_estimate_str));
+                        explanation_str = format!("Performance has {}.", self.green("improved"));
+                    }
+                    ComparisonResult::Regressed => {
+                        point_estimate_str = self.red(&self.bold(point_estimate_str));
+                        explanation_str = format!("Performance has {}.", self.red("regressed"));
+                    }
+                    ComparisonResult::NonSignificant => {
+                        explanation_str = "Change within noise threshold.".to_owned();
+                    }
+                }
+            }
+
+            let mut id = comp.id.as_title().to_owned();
+            if id.len() > 23 {
+                println!("{}", self.green(&id));
+                id.clear();
+            }
+            let id_len = id.len();
+
+            println!(
+                "{}{}change: [{} {} {}] (p = {:.2}, adjusted p = {:.2} {} {:.2})",
+                self.green(&id),
+                " ".repeat(24 - id_len),
+                self.faint(format::change(
+                    comp.change.confidence_interval.lower_bound,
+                    true
+                )),
+                point_estimate_str,
+                self.faint(format::change(
+                    comp.change.confidence_interval.upper_bound,
+                    true
+                )),
+                comp.p_value,
+                adjusted,
+                if changed { "<" } else { ">" },
+                comp.significance_threshold
+            );
+            println!("{}{}", " ".repeat(24), explanation_str);
+        }
+    }
+
+    fn relative_performance(&self, _context: &ReportContext, relative: &[RelativePerformance]) {
+        if matches!(self.verbosity, CliVerbosity::Quiet) {
+            return;
+        }
+
+        println!("\nRelative performance:");
+        for relative in relative {
+            self.print_relative_performance(relative);
+        }
+    }
 }
 
 pub struct BencherReport;
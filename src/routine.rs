@@ -12,7 +12,7 @@ pub(crate) trait Routine<M: Measurement, T: ?Sized> {
     /// PRIVATE
     fn bench(&mut self, m: &M, iters: &[u64], parameter: &T) -> Vec<f64>;
     /// PRIVATE
-    fn warm_up(&mut self, m: &M, how_long: Duration, parameter: &T) -> (u64, u64);
+    fn warm_up(&mut self, m: &M, how_long: Duration, adaptive: bool, parameter: &T) -> (u64, u64);
 
     /// PRIVATE
     fn test(&mut self, m: &M, parameter: &T) {
@@ -58,7 +58,8 @@ pub(crate) trait Routine<M: Measurement, T: ?Sized> {
         // being different code-paths even though they aren't really.
 
         // Get the warmup time for one second
-        let (wu_elapsed, wu_iters) = self.warm_up(measurement, Duration::from_secs(1), parameter);
+        let (wu_elapsed, wu_iters) =
+            self.warm_up(measurement, Duration::from_secs(1), false, parameter);
         if wu_elapsed < time {
             // Initial guess for the mean execution time
             let met = wu_elapsed as f64 / wu_iters as f64;
@@ -80,6 +81,45 @@ pub(crate) trait Routine<M: Measurement, T: ?Sized> {
         criterion.report.terminated(id, report_context);
     }
 
+    /// Warms up the routine for the configured warm-up time and returns the estimated mean
+    /// execution time of one iteration.
+    fn warm_up_estimate(
+        &mut self,
+        measurement: &M,
+        id: &BenchmarkId,
+        config: &BenchmarkConfig,
+        criterion: &Criterion<M>,
+        report_context: &ReportContext,
+        parameter: &T,
+    ) -> f64 {
+        let wu = config.warm_up_time;
+
+        criterion
+            .report
+            .warmup(id, report_context, wu.as_nanos() as f64);
+
+        if let Some(conn) = &criterion.connection {
+            conn.send(&OutgoingMessage::Warmup {
+                id: id.into(),
+                nanos: wu.as_nanos() as f64,
+            })
+            .unwrap();
+        }
+
+        let (wu_elapsed, wu_iters) =
+            self.warm_up(measurement, wu, config.adaptive_warm_up, parameter);
+        if crate::debug_enabled() {
+            println!(
+                "\nCompleted {} iterations in {} nanoseconds, estimated execution time is {} ns",
+                wu_iters,
+                wu_elapsed,
+                wu_elapsed as f64 / wu_iters as f64
+            );
+        }
+
+        wu_elapsed as f64 / wu_iters as f64
+    }
+
     fn sample(
         &mut self,
         measurement: &M,
@@ -130,78 +170,203 @@ pub(crate) trait Routine<M: Measurement, T: ?Sized> {
                 t_prev = t_now;
             }
         }
-        let wu = config.warm_up_time;
-        let m_ns = config.measurement_time.as_nanos();
+        // Initial guess for the mean execution time
+        let met = self.warm_up_estimate(
+            measurement,
+            id,
+            config,
+            criterion,
+            report_context,
+            parameter,
+        );
 
-        criterion
-            .report
-            .warmup(id, report_context, wu.as_nanos() as f64);
+        let n = config.sample_size as u64;
 
-        if let Some(conn) = &criterion.connection {
-            conn.send(&OutgoingMessage::Warmup {
-                id: id.into(),
-                nanos: wu.as_nanos() as f64,
-            })
-            .unwrap();
-        }
+        let actual_sampling_mode = config.sampling_mode.choose_sampling_mode(
+            met,
+            n,
+            config.measurement_time.as_nanos() as f64,
+        );
 
-        let (wu_elapsed, wu_iters) = self.warm_up(measurement, wu, parameter);
-        if crate::debug_enabled() {
-            println!(
-                "\nCompleted {} iterations in {} nanoseconds, estimated execution time is {} ns",
-                wu_iters,
-                wu_elapsed,
-                wu_elapsed as f64 / wu_iters as f64
-            );
-        }
+        let m_iters = actual_sampling_mode.iteration_counts(met, n, &config.measurement_time);
 
-        // Initial guess for the mean execution time
-        let met = wu_elapsed as f64 / wu_iters as f64;
+        report_measurement_start(id, criterion, report_context, met, &m_iters);
 
-        let n = config.sample_size as u64;
+        let (m_iters_f, m_elapsed) = self.sample_batch(criterion, &m_iters, parameter);
 
-        let actual_sampling_mode = config
-            .sampling_mode
-            .choose_sampling_mode(met, n, m_ns as f64);
+        (actual_sampling_mode, m_iters_f, m_elapsed)
+    }
 
-        let m_iters = actual_sampling_mode.iteration_counts(met, n, &config.measurement_time);
+    /// Takes one sample for each of the given iteration counts.
+    fn sample_batch(
+        &mut self,
+        criterion: &Criterion<M>,
+        m_iters: &[u64],
+        parameter: &T,
+    ) -> (Box<[f64]>, Box<[f64]>) {
+        let m_elapsed = self.bench(&criterion.measurement, m_iters, parameter);
 
-        let expected_ns = m_iters
-            .iter()
-            .copied()
-            .map(|count| count as f64 * met)
-            .sum();
-
-        // Use saturating_add to handle overflow.
-        let mut total_iters = 0u64;
-        for count in m_iters.iter().copied() {
-            total_iters = total_iters.saturating_add(count);
-        }
+        let m_iters_f: Vec<f64> = m_iters.iter().map(|&x| x as f64).collect();
 
-        criterion
-            .report
-            .measurement_start(id, report_context, n, expected_ns, total_iters);
+        (m_iters_f.into_boxed_slice(), m_elapsed.into_boxed_slice())
+    }
+}
 
-        if let Some(conn) = &criterion.connection {
-            conn.send(&OutgoingMessage::MeasurementStart {
-                id: id.into(),
-                sample_count: n,
-                estimate_ns: expected_ns,
-                iter_count: total_iters,
-            })
-            .unwrap();
+pub(crate) fn report_measurement_start<M: Measurement>(
+    id: &BenchmarkId,
+    criterion: &Criterion<M>,
+    report_context: &ReportContext,
+    met: f64,
+    m_iters: &[u64],
+) {
+    let n = m_iters.len() as u64;
+    let expected_ns = m_iters
+        .iter()
+        .copied()
+        .map(|count| count as f64 * met)
+        .sum();
+
+    // Use saturating_add to handle overflow.
+    let mut total_iters = 0u64;
+    for count in m_iters.iter().copied() {
+        total_iters = total_iters.saturating_add(count);
+    }
+
+    criterion
+        .report
+        .measurement_start(id, report_context, n, expected_ns, total_iters);
+
+    if let Some(conn) = &criterion.connection {
+        conn.send(&OutgoingMessage::MeasurementStart {
+            id: id.into(),
+            sample_count: n,
+            estimate_ns: expected_ns,
+            iter_count: total_iters,
+        })
+        .unwrap();
+    }
+}
+
+/// Samples several routines at once, interleaving their measurements (A, B, A, B...) rather than
+/// measuring each routine to completion before starting the next. Any slow drift in the
+/// performance of the machine (eg. due to thermal throttling) then affects all of the routines
+/// equally, so that their samples can be compared pairwise.
+///
+/// Each routine is given the full measurement time, so this takes about as long as sampling the
+/// routines one after another.
+#[allow(clippy::type_complexity)]
+pub(crate) fn sample_interleaved<M: Measurement>(
+    routines: &mut [(&BenchmarkId, &mut dyn Routine<M, ()>)],
+    config: &BenchmarkConfig,
+    criterion: &Criterion<M>,
+    report_context: &ReportContext,
+) -> Vec<(ActualSamplingMode, Box<[f64]>, Box<[f64]>)> {
+    let measurement = &criterion.measurement;
+    let n = config.sample_size as u64;
+    let m_ns = config.measurement_time.as_nanos() as f64;
+
+    let mets = routines
+        .iter_mut()
+        .map(|(id, routine)| {
+            routine.warm_up_estimate(measurement, id, config, criterion, report_context, &())
+        })
+        .collect::<Vec<_>>();
+
+    // All of the routines must use the same sampling mode so that their samples can be paired up.
+    let modes = mets
+        .iter()
+        .map(|&met| config.sampling_mode.choose_sampling_mode(met, n, m_ns))
+        .collect::<Vec<_>>();
+    let actual_sampling_mode = if modes.iter().all(|mode| *mode == modes[0]) {
+        modes[0].clone()
+    } else {
+        ActualSamplingMode::Flat
+    };
+
+    let m_iters = mets
+        .iter()
+        .map(|&met| actual_sampling_mode.iteration_counts(met, n, &config.measurement_time))
+        .collect::<Vec<_>>();
+
+    for ((id, _), (&met, iters)) in routines.iter().zip(mets.iter().zip(m_iters.iter())) {
+        report_measurement_start(id, criterion, report_context, met, iters);
+    }
+
+    let sample_count = m_iters.first().map_or(0, Vec::len);
+    let mut m_elapsed = vec![Vec::with_capacity(sample_count); routines.len()];
+    for i in 0..sample_count {
+        for ((_, routine), (iters, elapsed)) in routines
+            .iter_mut()
+            .zip(m_iters.iter().zip(m_elapsed.iter_mut()))
+        {
+            elapsed.push(routine.bench(measurement, &[iters[i]], &())[0]);
         }
+    }
 
-        let m_elapsed = self.bench(measurement, &m_iters, parameter);
+    m_iters
+        .into_iter()
+        .zip(m_elapsed)
+        .map(|(iters, elapsed)| {
+            let iters: Vec<f64> = iters.iter().map(|&x| x as f64).collect();
+            (
+                actual_sampling_mode.clone(),
+                iters.into_boxed_slice(),
+                elapsed.into_boxed_slice(),
+            )
+        })
+        .collect()
+}
 
-        let m_iters_f: Vec<f64> = m_iters.iter().map(|&x| x as f64).collect();
+/// With adaptive warm-up, batches must take at least this fraction of the warm-up time.
+const ADAPTIVE_WARM_UP_BATCHES: u32 = 1000;
+
+/// The number of batches the warm-up must take, once they are long enough, before it can be
+/// considered steady.
+const STEADY_STATE_MIN_BATCHES: usize = 40;
+
+/// The largest change in the time per iteration across the second half of the warm-up, relative
+/// to its mean, for the warm-up to be considered steady.
+const STEADY_STATE_TOLERANCE: f64 = 0.01;
+
+/// The number of standard errors added to the slope to bound the trend from above.
+const STEADY_STATE_T: f64 = 2.0;
+
+/// Returns true if the time per iteration of the warm-up batches so far has settled. A line is
+/// fitted to the second half of the batches, and the change along it, taking the upper end of its
+/// confidence interval, must be within the tolerance. Not finding a significant trend isn't
+/// enough, since a slow one is hidden by the noise of a few batches: the trend has to be shown to
+/// be small. As the warm-up goes on, the half that is checked grows with it, so a slow trend is
+/// eventually resolved.
+fn is_steady(times: &[f64]) -> bool {
+    if times.len() < STEADY_STATE_MIN_BATCHES {
+        return false;
+    }
+
+    let times = &times[times.len() / 2..];
+    let n = times.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = times.iter().sum::<f64>() / n;
 
-        (
-            actual_sampling_mode,
-            m_iters_f.into_boxed_slice(),
-            m_elapsed.into_boxed_slice(),
-        )
+    let (mut sxx, mut sxy) = (0.0, 0.0);
+    for (x, &y) in times.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        sxx += dx * dx;
+        sxy += dx * (y - mean_y);
     }
+    let slope = sxy / sxx;
+
+    let ssr = times
+        .iter()
+        .enumerate()
+        .map(|(x, &y)| {
+            let residual = y - mean_y - slope * (x as f64 - mean_x);
+            residual * residual
+        })
+        .sum::<f64>();
+    let standard_error = (ssr / (n - 2.0) / sxx).sqrt();
+
+    let change = (slope.abs() + STEADY_STATE_T * standard_error) * (n - 1.0);
+    change <= STEADY_STATE_TOLERANCE * mean_y
 }
 
 pub struct Function<M: Measurement, F, T>
@@ -255,7 +420,7 @@ where
             .collect()
     }
 
-    fn warm_up(&mut self, m: &M, how_long: Duration, parameter: &T) -> (u64, u64) {
+    fn warm_up(&mut self, m: &M, how_long: Duration, adaptive: bool, parameter: &T) -> (u64, u64) {
         let f = &mut self.f;
         let mut b = Bencher {
             iterated: false,
@@ -267,6 +432,10 @@ where
 
         let mut total_iters = 0;
         let mut elapsed_time = Duration::from_millis(0);
+        // With adaptive warm-up, the batch size stops doubling once a batch is long enough to be
+        // timed reliably. The warm-up then ends as soon as the batches have settled.
+        let min_batch_time = how_long / ADAPTIVE_WARM_UP_BATCHES;
+        let mut batches: Vec<(Duration, u64)> = vec![];
         loop {
             (*f)(&mut b, black_box(parameter));
 
@@ -278,7 +447,75 @@ where
                 return (elapsed_time.as_nanos() as u64, total_iters);
             }
 
+            if adaptive && b.elapsed_time >= min_batch_time {
+                batches.push((b.elapsed_time, b.iters));
+
+                let times = batches
+                    .iter()
+                    .map(|&(elapsed, iters)| elapsed.as_nanos() as f64 / iters as f64)
+                    .collect::<Vec<_>>();
+                if is_steady(&times) {
+                    if crate::debug_enabled() {
+                        println!(
+                            "\nReached steady state after {} iterations in {} nanoseconds",
+                            total_iters,
+                            elapsed_time.as_nanos()
+                        );
+                    }
+                    // Estimate the execution time from the steady half only.
+                    let (steady_elapsed, steady_iters) = batches[batches.len() / 2..]
+                        .iter()
+                        .fold((Duration::ZERO, 0), |(elapsed, iters), &(e, i)| {
+                            (elapsed + e, iters + i)
+                        });
+                    return (steady_elapsed.as_nanos() as u64, steady_iters);
+                }
+                continue;
+            }
+
             b.iters = b.iters.wrapping_mul(2);
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{is_steady, STEADY_STATE_MIN_BATCHES};
+
+    // Times per iteration around 10, with relative noise of up to `noise` which repeats every few
+    // batches
+    fn times(n: usize, noise: f64, trend: impl Fn(usize) -> f64) -> Vec<f64> {
+        (0..n)
+            .map(|i| (10.0 + trend(i)) * (1.0 + [0.0, 1.0, -0.5, 0.5, -1.0][i % 5] * noise))
+            .collect()
+    }
+
+    #[test]
+    fn test_flat_times_are_steady() {
+        let times = times(STEADY_STATE_MIN_BATCHES, 0.002, |_| 0.0);
+        assert!(is_steady(&times));
+        assert!(!is_steady(&times[..STEADY_STATE_MIN_BATCHES - 1]));
+    }
+
+    #[test]
+    fn test_trending_times_are_not_steady() {
+        let times = times(100, 0.002, |i| 10.0 - 0.1 * i as f64);
+        assert!(!is_steady(&times));
+    }
+
+    #[test]
+    fn test_identical_times_are_steady() {
+        assert!(is_steady(&[5.0; STEADY_STATE_MIN_BATCHES]));
+    }
+
+    #[test]
+    fn test_slow_decay_is_not_steady() {
+        // Decays by 0.5% per batch, too slowly for its slope to be significant over ten batches
+        // with 2% noise, although after ten batches it is still 24% slower than the steady state
+        let times = times(3000, 0.02, |i| 2.5 * 0.995f64.powi(i as i32));
+        let first_steady = (1..=times.len()).find(|&n| is_steady(&times[..n])).unwrap();
+        assert!(first_steady > 1000);
+        // By then, what is left of the decay is within the tolerance
+        assert!(2.5 * 0.995f64.powi(first_steady as i32) < 0.1);
+    }
+}
@@ -0,0 +1,200 @@
+//! Control over the CPU affinity and scheduling priority of the measuring thread.
+//!
+//! These settings are only applied while a benchmark is being sampled. Threads inherit the
+//! affinity of the thread that spawned them, so pinning for the whole run would also pin the
+//! rayon threads used by the analysis to a single core.
+
+use std::sync::Once;
+
+use serde::{Deserialize, Serialize};
+
+// The settings are applied for every benchmark, but a failure to apply them is only worth
+// reporting once per process.
+static AFFINITY_WARNING: Once = Once::new();
+static NICE_WARNING: Once = Once::new();
+
+/// The scheduling settings requested by the user.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct SchedulingConfig {
+    pub(crate) cpu: Option<usize>,
+    pub(crate) nice: Option<i32>,
+}
+
+/// The scheduling settings that were actually in effect while sampling. These are stored
+/// alongside the sample so that results can be traced back to how they were measured.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) struct AppliedScheduling {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cpu_affinity: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) nice: Option<i32>,
+}
+
+/// Applies a `SchedulingConfig` to the current thread and restores the previous settings when
+/// dropped.
+pub(crate) struct SchedulingGuard {
+    applied: AppliedScheduling,
+    previous_affinity: Option<sys::CpuSet>,
+    previous_nice: Option<i32>,
+}
+impl SchedulingGuard {
+    pub(crate) fn apply(config: &SchedulingConfig) -> SchedulingGuard {
+        let mut guard = SchedulingGuard {
+            applied: AppliedScheduling::default(),
+            previous_affinity: None,
+            previous_nice: None,
+        };
+
+        if let Some(cpu) = config.cpu {
+            match sys::pin_to_cpu(cpu) {
+                Ok(previous) => {
+                    guard.previous_affinity = Some(previous);
+                    guard.applied.cpu_affinity = Some(cpu);
+                }
+                Err(e) => AFFINITY_WARNING.call_once(|| {
+                    eprintln!(
+                        "\nWarning: Unable to pin benchmark thread to CPU {}: {}",
+                        cpu, e
+                    )
+                }),
+            }
+        }
+
+        if let Some(nice) = config.nice {
+            match sys::set_nice(nice) {
+                Ok(previous) => {
+                    guard.previous_nice = Some(previous);
+                    guard.applied.nice = Some(nice);
+                }
+                Err(e) => NICE_WARNING.call_once(|| {
+                    eprintln!(
+                        "\nWarning: Unable to set scheduling priority of benchmark thread to {}: {}",
+                        nice, e
+                    )
+                }),
+            }
+        }
+
+        guard
+    }
+
+    pub(crate) fn applied(&self) -> AppliedScheduling {
+        self.applied
+    }
+}
+impl Drop for SchedulingGuard {
+    fn drop(&mut self) {
+        // Restoring a higher priority (a lower nice value) isn't permitted for unprivileged users,
+        // so after a positive nice value the thread keeps it. Errors are deliberately ignored
+        // here; the setting is applied again for every benchmark anyway.
+        if let Some(nice) = self.previous_nice.take() {
+            let _ = sys::set_nice(nice);
+        }
+        if let Some(affinity) = self.previous_affinity.take() {
+            let _ = sys::set_affinity(&affinity);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::io;
+    use std::mem;
+
+    pub(crate) type CpuSet = libc::cpu_set_t;
+
+    fn current_thread_id() -> libc::id_t {
+        unsafe { libc::syscall(libc::SYS_gettid) as libc::id_t }
+    }
+
+    fn get_affinity() -> io::Result<CpuSet> {
+        let mut set: CpuSet = unsafe { mem::zeroed() };
+        let ret = unsafe { libc::sched_getaffinity(0, mem::size_of::<CpuSet>(), &mut set) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(set)
+    }
+
+    pub(crate) fn set_affinity(set: &CpuSet) -> io::Result<()> {
+        let ret = unsafe { libc::sched_setaffinity(0, mem::size_of::<CpuSet>(), set) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Pins the calling thread to the given core, returning the previous affinity mask.
+    pub(crate) fn pin_to_cpu(cpu: usize) -> io::Result<CpuSet> {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "CPU index is out of range",
+            ));
+        }
+
+        let previous = get_affinity()?;
+        let mut set: CpuSet = unsafe { mem::zeroed() };
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+        set_affinity(&set)?;
+        Ok(previous)
+    }
+
+    /// Sets the nice value of the calling thread, returning the previous value.
+    pub(crate) fn set_nice(nice: i32) -> io::Result<i32> {
+        let tid = current_thread_id();
+
+        // The getpriority wrapper can legitimately return -1, but the raw system call returns
+        // 20 - nice, which is always positive, so an error is unambiguous.
+        let ret = unsafe { libc::syscall(libc::SYS_getpriority, libc::PRIO_PROCESS, tid) };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let previous = 20 - ret as i32;
+
+        let ret = unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, nice) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(previous)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::io;
+
+    pub(crate) type CpuSet = ();
+
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "this is only supported on Linux",
+        )
+    }
+
+    pub(crate) fn set_affinity(_set: &CpuSet) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    pub(crate) fn pin_to_cpu(_cpu: usize) -> io::Result<CpuSet> {
+        Err(unsupported())
+    }
+
+    pub(crate) fn set_nice(_nice: i32) -> io::Result<i32> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use super::sys;
+
+    #[test]
+    fn set_nice_returns_the_previous_nice_value() {
+        let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) };
+        // Setting the same nice value again is always permitted
+        assert_eq!(nice, sys::set_nice(nice).unwrap());
+        assert_eq!(nice, sys::set_nice(nice).unwrap());
+    }
+}
@@ -6,6 +6,7 @@ mod resamples;
 
 use crate::stats::bivariate::resamples::Resamples;
 use crate::stats::float::Float;
+use crate::stats::rand_util::{new_seed, task_rng};
 use crate::stats::tuple::{Tuple, TupledDistributionsBuilder};
 use crate::stats::univariate::Sample;
 #[cfg(feature = "rayon")]
@@ -73,13 +74,17 @@ where
         T::Distributions: Send,
         T::Builder: Send,
     {
+        let seed = new_seed();
         #[cfg(feature = "rayon")]
         {
             (0..nresamples)
                 .into_par_iter()
                 .map_init(
                     || Resamples::new(*self),
-                    |resamples, _| statistic(resamples.next()),
+                    |resamples, i| {
+                        resamples.set_rng(task_rng(seed, i));
+                        statistic(resamples.next())
+                    },
                 )
                 .fold(
                     || T::Builder::new(0),
@@ -101,7 +106,10 @@ where
         {
             let mut resamples = Resamples::new(*self);
             (0..nresamples)
-                .map(|_| statistic(resamples.next()))
+                .map(|i| {
+                    resamples.set_rng(task_rng(seed, i));
+                    statistic(resamples.next())
+                })
                 .fold(T::Builder::new(0), |mut sub_distributions, sample| {
                     sub_distributions.push(sample);
                     sub_distributions
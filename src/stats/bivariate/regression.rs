@@ -51,3 +51,86 @@ where
         _1 - ss_res / ss_tot
     }
 }
+
+/// A straight line with an intercept `y = intercept + slope * x`
+#[derive(Clone, Copy)]
+pub struct Line<A>
+where
+    A: Float,
+{
+    /// The value of `y` at `x = 0`
+    pub intercept: A,
+    /// The change in `y` per unit of `x`
+    pub slope: A,
+}
+
+impl<A> Line<A>
+where
+    A: Float,
+{
+    /// Fits the data to a straight line using ordinary least squares
+    ///
+    /// The slope is NaN if all the `x` values are equal.
+    ///
+    /// - Time: `O(length)`
+    pub fn fit(data: &Data<'_, A, A>) -> Line<A> {
+        let _0 = A::cast(0);
+        let xs = data.0;
+        let ys = data.1;
+
+        let n = A::cast(xs.len());
+        let x_bar = crate::stats::sum(xs) / n;
+        let y_bar = crate::stats::sum(ys) / n;
+
+        let (sxy, sxx) = data.iter().fold((_0, _0), |(sxy, sxx), (&x, &y)| {
+            (sxy + (x - x_bar) * (y - y_bar), sxx + (x - x_bar).powi(2))
+        });
+
+        let slope = sxy / sxx;
+        Line {
+            intercept: y_bar - slope * x_bar,
+            slope,
+        }
+    }
+
+    /// Computes the goodness of fit (coefficient of determination) for this data set
+    ///
+    /// - Time: `O(length)`
+    pub fn r_squared(&self, data: &Data<'_, A, A>) -> A {
+        let _0 = A::cast(0);
+        let _1 = A::cast(1);
+        let ys = data.1;
+
+        let n = A::cast(ys.len());
+        let y_bar = crate::stats::sum(ys) / n;
+
+        let (ss_res, ss_tot) = data.iter().fold((_0, _0), |(ss_res, ss_tot), (&x, &y)| {
+            (
+                ss_res + (y - self.intercept - self.slope * x).powi(2),
+                ss_tot + (y - y_bar).powi(2),
+            )
+        });
+
+        _1 - ss_res / ss_tot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::Line;
+    use crate::stats::bivariate::Data;
+
+    #[test]
+    fn line_recovers_intercept() {
+        let xs = (1..=20).map(f64::from).collect::<Vec<_>>();
+        let ys = xs.iter().map(|x| 50. + 2. * x).collect::<Vec<_>>();
+        let data = Data::new(&xs, &ys);
+        let line = Line::fit(&data);
+
+        assert_relative_eq!(line.intercept, 50., epsilon = 1e-9);
+        assert_relative_eq!(line.slope, 2., epsilon = 1e-9);
+        assert_relative_eq!(line.r_squared(&data), 1., epsilon = 1e-9);
+    }
+}
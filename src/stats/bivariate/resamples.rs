@@ -26,6 +26,11 @@ where
         }
     }
 
+    /// Replaces the generator used to draw the resamples
+    pub fn set_rng(&mut self, rng: Rng) {
+        self.rng = rng;
+    }
+
     pub fn next(&mut self) -> Data<'_, X, Y> {
         let n = self.data.0.len();
 
@@ -0,0 +1,244 @@
+//! Hierarchical (two-level) bootstrap
+//!
+//! A sample made up of several independent runs has two sources of variance: the variance of the
+//! measurements within each run, and the variance between runs. Resampling the pooled
+//! measurements only captures the former. Instead, each resample first draws runs with
+//! replacement and then draws measurements with replacement from within each of the drawn runs.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::stats::rand_util::{new_rng, new_seed, task_rng, Rng};
+use crate::stats::tuple::{Tuple, TupledDistributionsBuilder};
+
+/// Generates hierarchical resamples of the indices of a sample. The sample is made up of
+/// consecutive runs with the given lengths.
+pub struct Resamples {
+    rng: Rng,
+    runs: Vec<(usize, usize)>,
+    stage: Vec<usize>,
+}
+
+#[allow(clippy::should_implement_trait)]
+impl Resamples {
+    /// # Panics
+    ///
+    /// Panics if there are no runs or any run is empty
+    pub fn new(run_lengths: &[usize]) -> Resamples {
+        assert!(!run_lengths.is_empty() && run_lengths.iter().all(|&len| len > 0));
+
+        let mut start = 0;
+        let runs = run_lengths
+            .iter()
+            .map(|&len| {
+                let run = (start, len);
+                start += len;
+                run
+            })
+            .collect::<Vec<_>>();
+
+        Resamples {
+            rng: new_rng(),
+            runs,
+            stage: Vec::with_capacity(start),
+        }
+    }
+
+    /// Replaces the generator used to draw the resamples
+    pub fn set_rng(&mut self, rng: Rng) {
+        self.rng = rng;
+    }
+
+    /// Returns the indices of the next resample
+    pub fn next(&mut self) -> &[usize] {
+        let nruns = self.runs.len() as u64;
+
+        self.stage.clear();
+        for _ in 0..nruns {
+            let (start, len) = self.runs[self.rng.rand_range(0..nruns) as usize];
+            for _ in 0..len {
+                let offset = self.rng.rand_range(0..len as u64) as usize;
+                self.stage.push(start + offset);
+            }
+        }
+
+        &self.stage
+    }
+}
+
+/// Returns the bootstrap distributions of the parameters estimated by the `statistic`, which is
+/// given the indices of each hierarchical resample
+///
+/// - Multi-threaded
+/// - Time: `O(nresamples)`
+/// - Memory: `O(nresamples)`
+pub fn bootstrap<T, S>(run_lengths: &[usize], nresamples: usize, statistic: S) -> T::Distributions
+where
+    S: Fn(&[usize]) -> T + Sync,
+    T: Tuple + Send,
+    T::Distributions: Send,
+    T::Builder: Send,
+{
+    let seed = new_seed();
+    #[cfg(feature = "rayon")]
+    {
+        (0..nresamples)
+            .into_par_iter()
+            .map_init(
+                || Resamples::new(run_lengths),
+                |resamples, i| {
+                    resamples.set_rng(task_rng(seed, i));
+                    statistic(resamples.next())
+                },
+            )
+            .fold(
+                || T::Builder::new(0),
+                |mut sub_distributions, sample| {
+                    sub_distributions.push(sample);
+                    sub_distributions
+                },
+            )
+            .reduce(
+                || T::Builder::new(0),
+                |mut a, mut b| {
+                    a.extend(&mut b);
+                    a
+                },
+            )
+            .complete()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut resamples = Resamples::new(run_lengths);
+        (0..nresamples)
+            .map(|i| {
+                resamples.set_rng(task_rng(seed, i));
+                statistic(resamples.next())
+            })
+            .fold(T::Builder::new(0), |mut sub_distributions, sample| {
+                sub_distributions.push(sample);
+                sub_distributions
+            })
+            .complete()
+    }
+}
+
+/// Two-sample version of [`bootstrap`]; the `statistic` is given the indices of a hierarchical
+/// resample of each sample
+///
+/// - Multi-threaded
+/// - Time: `O(nresamples)`
+/// - Memory: `O(nresamples)`
+pub fn bootstrap2<T, S>(
+    a_run_lengths: &[usize],
+    b_run_lengths: &[usize],
+    nresamples: usize,
+    statistic: S,
+) -> T::Distributions
+where
+    S: Fn(&[usize], &[usize]) -> T + Sync,
+    T: Tuple + Send,
+    T::Distributions: Send,
+    T::Builder: Send,
+{
+    let seed = new_seed();
+    #[cfg(feature = "rayon")]
+    {
+        (0..nresamples)
+            .into_par_iter()
+            .map_init(
+                || (Resamples::new(a_run_lengths), Resamples::new(b_run_lengths)),
+                |(a_resamples, b_resamples), i| {
+                    a_resamples.set_rng(task_rng(seed, 2 * i));
+                    b_resamples.set_rng(task_rng(seed, 2 * i + 1));
+                    statistic(a_resamples.next(), b_resamples.next())
+                },
+            )
+            .fold(
+                || T::Builder::new(0),
+                |mut sub_distributions, sample| {
+                    sub_distributions.push(sample);
+                    sub_distributions
+                },
+            )
+            .reduce(
+                || T::Builder::new(0),
+                |mut a, mut b| {
+                    a.extend(&mut b);
+                    a
+                },
+            )
+            .complete()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut a_resamples = Resamples::new(a_run_lengths);
+        let mut b_resamples = Resamples::new(b_run_lengths);
+        (0..nresamples)
+            .map(|i| {
+                a_resamples.set_rng(task_rng(seed, 2 * i));
+                b_resamples.set_rng(task_rng(seed, 2 * i + 1));
+                statistic(a_resamples.next(), b_resamples.next())
+            })
+            .fold(T::Builder::new(0), |mut sub_distributions, sample| {
+                sub_distributions.push(sample);
+                sub_distributions
+            })
+            .complete()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use quickcheck::quickcheck;
+    use quickcheck::TestResult;
+
+    use super::Resamples;
+
+    // Check that each resample is made up of whole runs of the original lengths, with every index
+    // drawn from within its run
+    quickcheck! {
+        fn indices_within_runs(lengths: Vec<u8>, nresamples: u8) -> TestResult {
+            let lengths = lengths
+                .into_iter()
+                .map(|len| len as usize)
+                .filter(|&len| len > 0)
+                .collect::<Vec<_>>();
+            if lengths.is_empty() {
+                return TestResult::discard();
+            }
+            let total: usize = lengths.iter().sum();
+            let min_len = *lengths.iter().min().unwrap();
+            let max_len = *lengths.iter().max().unwrap();
+            let mut resamples = Resamples::new(&lengths);
+
+            TestResult::from_bool((0..nresamples).all(|_| {
+                let indices = resamples.next();
+                indices.len() >= min_len * lengths.len()
+                    && indices.len() <= max_len * lengths.len()
+                    && indices.iter().all(|&i| i < total)
+            }))
+        }
+    }
+
+    #[test]
+    fn runs_are_not_mixed() {
+        let lengths = [5, 3, 7];
+        let mut resamples = Resamples::new(&lengths);
+
+        for _ in 0..1000 {
+            let indices = resamples.next().to_vec();
+            let mut rest = &indices[..];
+            while !rest.is_empty() {
+                let run = match rest[0] {
+                    0..=4 => (0, 5),
+                    5..=7 => (5, 3),
+                    _ => (8, 7),
+                };
+                let (chunk, tail) = rest.split_at(run.1);
+                assert!(chunk.iter().all(|&i| i >= run.0 && i < run.0 + run.1));
+                rest = tail;
+            }
+        }
+    }
+}
@@ -9,11 +9,13 @@
 mod test;
 
 pub mod bivariate;
+pub mod hierarchical;
+pub mod normal;
 pub mod tuple;
 pub mod univariate;
 
 mod float;
-mod rand_util;
+pub(crate) mod rand_util;
 
 use std::mem;
 use std::ops::Deref;
@@ -75,6 +77,48 @@ where
     }
 }
 
+impl Distribution<f64> {
+    /// Computes the bias-corrected and accelerated confidence interval of the population
+    /// parameter, whose value in the original sample is `point_estimate`
+    ///
+    /// The bias correction accounts for the bootstrap distribution not being centred on the point
+    /// estimate, and the `acceleration` (see [`univariate::jackknife::acceleration`]) for the
+    /// standard error of the estimate changing with the value of the parameter. Both shift the
+    /// percentiles used for the interval. Falls back to the percentile interval if the point
+    /// estimate lies outside of the distribution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `confidence_level` is not in the `(0, 1)` range.
+    pub fn bca_confidence_interval(
+        &self,
+        point_estimate: f64,
+        acceleration: f64,
+        confidence_level: f64,
+    ) -> (f64, f64) {
+        assert!(confidence_level > 0. && confidence_level < 1.);
+
+        let n = self.0.len() as f64;
+        let below = self.0.iter().filter(|&&x| x < point_estimate).count() as f64;
+        let equal = self.0.iter().filter(|&&x| x == point_estimate).count() as f64;
+        let bias = normal::quantile((below + equal / 2.) / n);
+
+        let adjust = |alpha: f64| {
+            let z = bias + normal::quantile(alpha);
+            normal::cdf(bias + z / (1. - acceleration * z))
+        };
+        let lower = adjust((1. - confidence_level) / 2.);
+        let upper = adjust((1. + confidence_level) / 2.);
+
+        if !(lower.is_finite() && upper.is_finite()) {
+            return self.confidence_interval(confidence_level);
+        }
+
+        let percentiles = self.percentiles();
+        (percentiles.at(100. * lower), percentiles.at(100. * upper))
+    }
+}
+
 impl<A> Deref for Distribution<A> {
     type Target = Sample<A>;
 
@@ -12,10 +12,81 @@ thread_local! {
     ));
 }
 
+/// Restarts the generators created on this thread from the given seed. Each stage of the
+/// analysis uses its own stream, so that skipping one stage doesn't change the results of the
+/// next.
+pub fn reseed(seed: u64, stream: u64) {
+    SEED_RAND.with(|r| *r.borrow_mut() = Rand64::new(mix(u128::from(seed), stream)));
+}
+
 pub fn new_rng() -> Rng {
+    Rand64::new(new_seed())
+}
+
+/// Draws the seed of a parallel computation, from which each of its tasks derives its own
+/// generator with [`task_rng`].
+pub fn new_seed() -> u128 {
     SEED_RAND.with(|r| {
         let mut r = r.borrow_mut();
-        let seed = ((r.rand_u64() as u128) << 64) | (r.rand_u64() as u128);
-        Rand64::new(seed)
+        ((r.rand_u64() as u128) << 64) | (r.rand_u64() as u128)
     })
 }
+
+/// The generator of the `index`-th task of a parallel computation. It depends only on the seed
+/// of the computation and the index of the task, not on the thread that runs the task, so the
+/// results are reproducible however the tasks are scheduled.
+pub fn task_rng(seed: u128, index: usize) -> Rng {
+    Rand64::new(mix(seed, index as u64))
+}
+
+/// Derives the state of a generator from a seed and an index. PCG generators which only differ in
+/// their increment produce correlated sequences, so the index is mixed into the state instead,
+/// with the `SplitMix64` finalizer making nearby seeds and indices unrelated.
+fn mix(seed: u128, index: u64) -> u128 {
+    let high = splitmix64((seed >> 64) as u64 ^ splitmix64(index));
+    let low = splitmix64(seed as u64 ^ high);
+    (u128::from(high) << 64) | u128::from(low)
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{reseed, task_rng};
+    use crate::stats::univariate::{self, Sample};
+
+    #[test]
+    fn reseeded_bootstrap_is_reproducible() {
+        let v = (0..50).map(|i| f64::from(i * i % 17)).collect::<Vec<_>>();
+        let w = (0..40).map(|i| f64::from(i * 7 % 13)).collect::<Vec<_>>();
+        let (a, b) = (Sample::new(&v), Sample::new(&w));
+
+        let run = || {
+            reseed(42, 0);
+            let (means,) = a.bootstrap(1000, |s| (s.mean(),));
+            let (ratios,) = univariate::bootstrap(a, b, 1000, |a, b| (a.mean() / b.mean(),));
+            (means.to_vec(), ratios.to_vec())
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn task_generators_depend_only_on_seed_and_index() {
+        let draws = |seed: u128, index: usize| {
+            let mut rng = task_rng(seed, index);
+            (0..100).map(|_| rng.rand_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draws(42, 0), draws(42, 0));
+        assert_ne!(draws(42, 0), draws(42, 1));
+        assert_ne!(draws(42, 0), draws(43, 0));
+        // Swapping the seed and the index doesn't give the same generator
+        assert_ne!(draws(0, 1), draws(1, 0));
+    }
+}
@@ -0,0 +1,148 @@
+//! Diagnostics for samples whose measurements may not be independent
+//!
+//! Bootstrapping assumes that each measurement is independent of the ones taken before it. That
+//! isn't the case when the performance of the machine drifts during a run, eg. because of thermal
+//! throttling, so these look at the measurements in the order they were taken.
+
+use crate::stats::univariate::Sample;
+
+/// A point at which the mean of the sample shifts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Changepoint {
+    /// Index of the first measurement after the shift
+    pub index: usize,
+    /// Mean of the measurements before the shift
+    pub mean_before: f64,
+    /// Mean of the measurements from the shift onwards
+    pub mean_after: f64,
+    /// Welch's t statistic comparing the measurements after the shift against those before
+    pub t: f64,
+}
+
+/// Returns the autocorrelation of the sample at the given `lag`
+///
+/// Returns zero if the sample is constant or shorter than the lag.
+pub fn autocorrelation(sample: &Sample<f64>, lag: usize) -> f64 {
+    let n = sample.len();
+    if lag >= n {
+        return 0.0;
+    }
+
+    let mean = sample.mean();
+    let variance: f64 = sample.iter().map(|&x| (x - mean).powi(2)).sum();
+    if variance == 0.0 {
+        return 0.0;
+    }
+
+    let covariance: f64 = sample[..n - lag]
+        .iter()
+        .zip(&sample[lag..])
+        .map(|(&x, &y)| (x - mean) * (y - mean))
+        .sum();
+
+    covariance / variance
+}
+
+/// Returns the effective sample size: the number of independent measurements that would give
+/// the mean of the sample the same variance
+///
+/// This models the sample as a first-order autoregressive process, and is clamped to between one
+/// and the length of the sample.
+pub fn effective_sample_size(sample: &Sample<f64>) -> f64 {
+    let n = sample.len() as f64;
+    let rho = autocorrelation(sample, 1);
+
+    (n * (1.0 - rho) / (1.0 + rho)).clamp(1.0, n)
+}
+
+/// Finds the single point at which splitting the sample in two best explains its variance, ie.
+/// the split that minimizes the sum of the squared deviations from the mean of each part
+///
+/// Each part has at least `min_segment` measurements. Returns `None` if the sample is too short
+/// to be split.
+pub fn changepoint(sample: &Sample<f64>, min_segment: usize) -> Option<Changepoint> {
+    let n = sample.len();
+    let min_segment = min_segment.max(2);
+    if n < 2 * min_segment {
+        return None;
+    }
+
+    let total: f64 = sample.iter().sum();
+    let mut sum_before = sample[..min_segment - 1].iter().sum::<f64>();
+
+    // Minimizing the sum of squared deviations is the same as maximizing the weighted squared
+    // difference of the means of the two parts
+    let mut best: Option<(usize, f64)> = None;
+    for index in min_segment..=n - min_segment {
+        sum_before += sample[index - 1];
+        let (n_before, n_after) = (index as f64, (n - index) as f64);
+        let difference = sum_before / n_before - (total - sum_before) / n_after;
+        let score = n_before * n_after * difference * difference;
+        if best.map_or(true, |(_, best_score)| score > best_score) {
+            best = Some((index, score));
+        }
+    }
+
+    let (index, _) = best?;
+    let before = Sample::new(&sample[..index]);
+    let after = Sample::new(&sample[index..]);
+    let t = after.t(before);
+
+    Some(Changepoint {
+        index,
+        mean_before: before.mean(),
+        mean_after: after.mean(),
+        t: if t.is_finite() { t } else { 0.0 },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{autocorrelation, changepoint, effective_sample_size};
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn alternating_sample_is_negatively_correlated() {
+        let v = (0..100)
+            .map(|i| if i % 2 == 0 { 1.0 } else { -1.0 })
+            .collect::<Vec<_>>();
+        let sample = Sample::new(&v);
+        assert_relative_eq!(autocorrelation(sample, 1), -0.99);
+        assert_relative_eq!(effective_sample_size(sample), 100.0);
+    }
+
+    #[test]
+    fn trending_sample_is_positively_correlated() {
+        let v = (0..100).map(f64::from).collect::<Vec<_>>();
+        let sample = Sample::new(&v);
+        assert!(autocorrelation(sample, 1) > 0.9);
+        assert!(effective_sample_size(sample) < 5.0);
+    }
+
+    #[test]
+    fn constant_sample() {
+        let v = [3.0; 10];
+        let sample = Sample::new(&v);
+        assert_relative_eq!(autocorrelation(sample, 1), 0.0);
+        assert_relative_eq!(effective_sample_size(sample), 10.0);
+    }
+
+    #[test]
+    fn finds_step() {
+        let v = (0..100)
+            .map(|i| if i < 60 { 10.0 } else { 12.0 } + (i % 3) as f64 * 0.1)
+            .collect::<Vec<_>>();
+        let changepoint = changepoint(Sample::new(&v), 5).unwrap();
+        assert_eq!(changepoint.index, 60);
+        assert!(changepoint.mean_after > changepoint.mean_before);
+        assert!(changepoint.t > 10.0);
+    }
+
+    #[test]
+    fn short_sample_has_no_changepoint() {
+        let v = [1.0, 2.0, 3.0];
+        assert!(changepoint(Sample::new(&v), 2).is_none());
+    }
+}
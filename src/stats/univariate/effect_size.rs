@@ -0,0 +1,236 @@
+//! Effect sizes of the difference between two samples
+//!
+//! All of the effect sizes are oriented the same way: they are positive when the values in the
+//! first sample tend to be larger than those in the second.
+
+use crate::stats::univariate::nonparametric;
+use crate::stats::univariate::Sample;
+
+/// Cohen's d: the difference of the means in units of the pooled standard deviation. Each sample
+/// is made up of consecutive runs with the given lengths.
+///
+/// With several runs, the standard deviation of a sample includes the variance between its runs:
+/// the variance of the run means beyond what the variance within the runs accounts for is added
+/// to the variance within the runs. With a single run it is the ordinary standard deviation.
+///
+/// Returns zero if both samples are constant.
+pub fn cohens_d(a: &Sample<f64>, a_runs: &[usize], b: &Sample<f64>, b_runs: &[usize]) -> f64 {
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let pooled_var = ((n_a - 1.0) * variance_across_runs(a, a_runs)
+        + (n_b - 1.0) * variance_across_runs(b, b_runs))
+        / (n_a + n_b - 2.0);
+
+    if pooled_var > 0.0 {
+        (a.mean() - b.mean()) / pooled_var.sqrt()
+    } else {
+        0.0
+    }
+}
+
+// The variance of a single measurement of a sample taken in runs of the given lengths, made up
+// of the variance within the runs and the variance between them
+fn variance_across_runs(sample: &Sample<f64>, runs: &[usize]) -> f64 {
+    if runs.len() < 2 {
+        return sample.var(None);
+    }
+
+    let mut start = 0;
+    let mut within = 0.0;
+    let mut within_df = 0;
+    let mut run_means = Vec::with_capacity(runs.len());
+    for &len in runs {
+        let run = &sample[start..start + len];
+        start += len;
+        let mean = run.iter().sum::<f64>() / len as f64;
+        within += run.iter().map(|&x| (x - mean).powi(2)).sum::<f64>();
+        within_df += len - 1;
+        run_means.push(mean);
+    }
+    if within_df == 0 {
+        // Runs of one measurement each can't tell the two apart
+        return sample.var(None);
+    }
+    let within = within / within_df as f64;
+
+    // The spread of the run means includes the variance within runs, divided by their length
+    let run_means = Sample::new(&run_means);
+    let mean_inverse_length =
+        runs.iter().map(|&len| 1.0 / len as f64).sum::<f64>() / runs.len() as f64;
+    let between = (run_means.var(None) - within * mean_inverse_length).max(0.0);
+
+    within + between
+}
+
+/// The Hodges-Lehmann estimate of the shift between the samples: the median of the differences
+/// between every value of `a` and every value of `b`.
+///
+/// The differences aren't stored. With both samples sorted, they form a matrix whose rows are
+/// sorted, and the median is selected from it as described by Johnson and Mitchell (1978).
+///
+/// - Time: `O((length_a * log(length_a) + length_b) * log(length_a * length_b))`
+/// - Memory: `O(length_a + length_b)`
+pub fn hodges_lehmann(a: &Sample<f64>, b: &Sample<f64>) -> f64 {
+    let mut a = a.to_vec();
+    a.sort_unstable_by(f64::total_cmp);
+    // With `b` in descending order, each row `a[i] - b[..]` is ascending
+    let mut b = b.to_vec();
+    b.sort_unstable_by(|x, y| y.total_cmp(x));
+
+    // The same interpolation as `Percentiles::median`
+    let n = a.len() * b.len();
+    let lower = select_difference(&a, &b, (n - 1) / 2);
+    if n % 2 == 1 {
+        lower
+    } else {
+        (lower + select_difference(&a, &b, n / 2)) / 2.0
+    }
+}
+
+// Selects the `k`th smallest, counting from zero, of the differences `a[i] - b[j]`, where `a` is
+// ascending and `b` descending. Each row keeps the range of columns which may still hold the
+// answer. At every step the differences below and up to a pivot are counted; the pivot is the
+// median of the middles of the rows, weighted by their number of candidates, so at least a
+// quarter of the candidates are ruled out.
+fn select_difference(a: &[f64], b: &[f64], k: usize) -> f64 {
+    let mut low = vec![0; a.len()];
+    let mut high = vec![b.len(); a.len()];
+    let mut middles = Vec::with_capacity(a.len());
+
+    loop {
+        middles.clear();
+        middles.extend(
+            (0..a.len())
+                .filter(|&i| low[i] < high[i])
+                .map(|i| (a[i] - b[(low[i] + high[i]) / 2], high[i] - low[i])),
+        );
+        middles.sort_unstable_by(|x, y| x.0.total_cmp(&y.0));
+        let candidates = middles.iter().map(|&(_, count)| count).sum::<usize>();
+        let mut cumulative = 0;
+        let &(pivot, _) = middles
+            .iter()
+            .find(|&&(_, count)| {
+                cumulative += count;
+                2 * cumulative >= candidates
+            })
+            .unwrap();
+
+        let below = count_differences(a, b, |d| d < pivot);
+        let up_to = count_differences(a, b, |d| d <= pivot);
+        if k < below.iter().sum::<usize>() {
+            high = below;
+        } else if k >= up_to.iter().sum::<usize>() {
+            low = up_to;
+        } else {
+            return pivot;
+        }
+    }
+}
+
+// Counts the differences of each row which satisfy the `predicate`, which must hold for a prefix
+// of every row. Since `a` is ascending, the prefixes only get shorter from one row to the next.
+fn count_differences(a: &[f64], b: &[f64], predicate: impl Fn(f64) -> bool) -> Vec<usize> {
+    let mut j = b.len();
+    a.iter()
+        .map(|&x| {
+            while j > 0 && !predicate(x - b[j - 1]) {
+                j -= 1;
+            }
+            j
+        })
+        .collect()
+}
+
+/// The probability that a value drawn at random from `a` is larger than one drawn from `b`, with
+/// ties counting as half. This is also known as the common-language effect size.
+pub fn probability_of_superiority(a: &Sample<f64>, b: &Sample<f64>) -> f64 {
+    let (u, _) = nonparametric::mann_whitney_u(a, b);
+    u / (a.len() * b.len()) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{cohens_d, hodges_lehmann, probability_of_superiority};
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn cohens_d_of_shift() {
+        let a = [2.0, 3.0, 4.0];
+        let b = [1.0, 2.0, 3.0];
+        // Both samples have a variance of one
+        assert_relative_eq!(cohens_d(Sample::new(&a), &[3], Sample::new(&b), &[3]), 1.0);
+        assert_relative_eq!(cohens_d(Sample::new(&b), &[3], Sample::new(&a), &[3]), -1.0);
+    }
+
+    #[test]
+    fn cohens_d_of_constant_samples() {
+        let a = [2.0, 2.0, 2.0];
+        assert_relative_eq!(cohens_d(Sample::new(&a), &[3], Sample::new(&a), &[3]), 0.0);
+    }
+
+    #[test]
+    fn cohens_d_includes_variance_between_runs() {
+        // The runs of each sample are shifted apart by more than the spread within them
+        let a = [2.0, 3.0, 4.0, 12.0, 13.0, 14.0];
+        let b = [1.0, 2.0, 3.0, 11.0, 12.0, 13.0];
+        let (a, b) = (Sample::new(&a), Sample::new(&b));
+        let pooled = cohens_d(a, &[6], b, &[6]);
+        let in_runs = cohens_d(a, &[3, 3], b, &[3, 3]);
+        assert!(in_runs > 0.0 && in_runs < pooled);
+
+        // The variance within the runs is 1 and that of the run means is 50, of which 1/3 comes
+        // from the variance within runs of three measurements
+        assert_relative_eq!(in_runs, 1.0 / (1.0 + 50.0 - 1.0 / 3.0_f64).sqrt());
+    }
+
+    #[test]
+    fn hodges_lehmann_of_shift() {
+        let a = [11.0, 12.0, 13.0, 14.0, 1000.0];
+        let b = [1.0, 2.0, 3.0, 4.0, 5.0];
+        // The outlier moves the mean a long way, but not the median of the differences
+        assert_relative_eq!(hodges_lehmann(Sample::new(&a), Sample::new(&b)), 10.0);
+    }
+
+    #[test]
+    fn hodges_lehmann_matches_all_differences() {
+        // Rounded pseudo-random values, so that some of the differences are tied
+        let mut state = 1_u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((state >> 33) % 50) as f64 / 4.0
+        };
+
+        for &(n_a, n_b) in &[(2, 2), (2, 3), (5, 7), (10, 10), (13, 4), (31, 40)] {
+            let a = (0..n_a).map(|_| next()).collect::<Vec<_>>();
+            let b = (0..n_b).map(|_| next() + 3.0).collect::<Vec<_>>();
+            let differences = a
+                .iter()
+                .flat_map(|&x| b.iter().map(move |&y| x - y))
+                .collect::<Vec<_>>();
+            let expected = Sample::new(&differences).percentiles().median();
+
+            assert_relative_eq!(hodges_lehmann(Sample::new(&a), Sample::new(&b)), expected);
+        }
+    }
+
+    #[test]
+    fn probability_of_superiority_bounds() {
+        let a = [5.0, 6.0, 7.0];
+        let b = [1.0, 2.0, 3.0];
+        assert_relative_eq!(
+            probability_of_superiority(Sample::new(&a), Sample::new(&b)),
+            1.0
+        );
+        assert_relative_eq!(
+            probability_of_superiority(Sample::new(&b), Sample::new(&a)),
+            0.0
+        );
+        assert_relative_eq!(
+            probability_of_superiority(Sample::new(&a), Sample::new(&a)),
+            0.5
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Jackknife estimates, used to compute bias-corrected and accelerated bootstrap intervals
+
+use crate::stats::univariate::Sample;
+
+/// Returns the values of `statistic` on the sample with each of its measurements left out in turn
+///
+/// Returns an empty vector if the sample has fewer than three measurements.
+pub fn leave_one_out<S>(sample: &Sample<f64>, statistic: S) -> Vec<f64>
+where
+    S: Fn(&Sample<f64>) -> f64,
+{
+    let n = sample.len();
+    if n < 3 {
+        return vec![];
+    }
+
+    let mut rest = Vec::with_capacity(n - 1);
+    (0..n)
+        .map(|i| {
+            rest.clear();
+            rest.extend_from_slice(&sample[..i]);
+            rest.extend_from_slice(&sample[i + 1..]);
+            statistic(Sample::new(&rest))
+        })
+        .collect()
+}
+
+/// Estimates the acceleration of a bias-corrected and accelerated interval from the jackknife
+/// values of the statistic
+///
+/// Each group holds the jackknife values from leaving out the measurements of one of the samples
+/// the statistic depends on, in turn. Returns zero if the jackknife values don't vary.
+pub fn acceleration(groups: &[&[f64]]) -> f64 {
+    let (mut skew, mut spread) = (0., 0.);
+    for group in groups.iter().filter(|group| !group.is_empty()) {
+        let n = group.len() as f64;
+        let mean = group.iter().sum::<f64>() / n;
+        let (cubes, squares) = group.iter().fold((0., 0.), |(cubes, squares), &value| {
+            let u = (n - 1.) * (mean - value);
+            (cubes + u.powi(3), squares + u.powi(2))
+        });
+
+        skew += cubes / n.powi(3);
+        spread += squares / n.powi(2);
+    }
+
+    if spread > 0. {
+        skew / (6. * spread.powf(1.5))
+    } else {
+        0.
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{acceleration, leave_one_out};
+    use crate::stats::univariate::Sample;
+    use crate::stats::Distribution;
+
+    #[test]
+    fn mean_of_symmetric_sample_has_no_acceleration() {
+        let v = (0..21).map(f64::from).collect::<Vec<_>>();
+        let jackknife = leave_one_out(Sample::new(&v), |s| s.mean());
+        assert_eq!(jackknife.len(), 21);
+        assert_relative_eq!(acceleration(&[&jackknife]), 0., epsilon = 1e-12);
+    }
+
+    #[test]
+    fn skewed_sample_is_accelerated() {
+        let v = (0..50).map(|i| f64::from(i).exp2()).collect::<Vec<_>>();
+        let jackknife = leave_one_out(Sample::new(&v), |s| s.mean());
+        assert!(acceleration(&[&jackknife]) > 0.);
+    }
+
+    #[test]
+    fn bca_matches_percentile_without_bias_or_acceleration() {
+        let v = (0..1001).map(|i| f64::from(i) / 1000.).collect::<Vec<_>>();
+        let distribution = Distribution::from(v.into_boxed_slice());
+        let (lb, ub) = distribution.confidence_interval(0.9);
+        let (bca_lb, bca_ub) = distribution.bca_confidence_interval(0.5, 0., 0.9);
+        assert_relative_eq!(lb, bca_lb, epsilon = 1e-6);
+        assert_relative_eq!(ub, bca_ub, epsilon = 1e-6);
+
+        // A positive acceleration moves the interval up
+        let (accelerated_lb, accelerated_ub) = distribution.bca_confidence_interval(0.5, 0.1, 0.9);
+        assert!(accelerated_lb > lb && accelerated_ub > ub);
+    }
+}
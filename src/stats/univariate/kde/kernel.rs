@@ -26,6 +26,72 @@ where
     }
 }
 
+/// Epanechnikov kernel, scaled to unit variance
+///
+/// This is the kernel that minimizes the asymptotic mean integrated squared error, and unlike the
+/// Gaussian kernel it has a bounded support, `[-sqrt(5), sqrt(5)]`.
+#[derive(Clone, Copy)]
+pub struct Epanechnikov;
+
+impl<A> Kernel<A> for Epanechnikov
+where
+    A: Float,
+{
+    fn evaluate(&self, x: A) -> A {
+        let _5 = A::cast(5);
+        let u = x.powi(2) / _5;
+
+        if u < A::cast(1) {
+            A::cast(0.75_f32) * (A::cast(1) - u) / _5.sqrt()
+        } else {
+            A::cast(0)
+        }
+    }
+}
+
+/// Triangular kernel, scaled to unit variance
+///
+/// Its support is `[-sqrt(6), sqrt(6)]`.
+#[derive(Clone, Copy)]
+pub struct Triangular;
+
+impl<A> Kernel<A> for Triangular
+where
+    A: Float,
+{
+    fn evaluate(&self, x: A) -> A {
+        let width = A::cast(6).sqrt();
+        let u = x.abs() / width;
+
+        if u < A::cast(1) {
+            (A::cast(1) - u) / width
+        } else {
+            A::cast(0)
+        }
+    }
+}
+
+/// Uniform (rectangular) kernel, scaled to unit variance
+///
+/// Its support is `[-sqrt(3), sqrt(3)]`.
+#[derive(Clone, Copy)]
+pub struct Uniform;
+
+impl<A> Kernel<A> for Uniform
+where
+    A: Float,
+{
+    fn evaluate(&self, x: A) -> A {
+        let width = A::cast(3).sqrt();
+
+        if x.abs() <= width {
+            (A::cast(2) * width).recip()
+        } else {
+            A::cast(0)
+        }
+    }
+}
+
 #[cfg(test)]
 macro_rules! test {
     ($ty:ident) => {
@@ -73,6 +139,53 @@ macro_rules! test {
                     }
                 }
             }
+
+            mod bounded {
+                use approx::assert_relative_eq;
+
+                use crate::stats::univariate::kde::kernel::{
+                    Epanechnikov, Kernel, Triangular, Uniform,
+                };
+
+                // Integrates the kernel, and its second moment, over [-3 3]
+                fn moments<K: Kernel<$ty>>(kernel: K) -> ($ty, $ty) {
+                    const DX: $ty = 1e-4;
+
+                    let (mut mass, mut variance) = (0., 0.);
+                    let mut x = -3.;
+                    while x < 3. {
+                        let y = kernel.evaluate(x + DX / 2.);
+                        mass += DX * y;
+                        variance += DX * y * (x + DX / 2.).powi(2);
+                        x += DX;
+                    }
+
+                    (mass, variance)
+                }
+
+                #[test]
+                fn unit_mass_and_variance() {
+                    for (mass, variance) in
+                        [moments(Epanechnikov), moments(Triangular), moments(Uniform)]
+                    {
+                        assert_relative_eq!(mass, 1., epsilon = 1e-2);
+                        assert_relative_eq!(variance, 1., epsilon = 1e-2);
+                    }
+                }
+
+                #[test]
+                fn symmetric_and_bounded() {
+                    for x in [0.5, 1., 2.] {
+                        assert_relative_eq!(Epanechnikov.evaluate(-x), Epanechnikov.evaluate(x));
+                        assert_relative_eq!(Triangular.evaluate(-x), Triangular.evaluate(x));
+                        assert_relative_eq!(Uniform.evaluate(-x), Uniform.evaluate(x));
+                    }
+
+                    assert_eq!(Epanechnikov.evaluate(2.5 as $ty), 0.);
+                    assert_eq!(Triangular.evaluate(2.5 as $ty), 0.);
+                    assert_eq!(Uniform.evaluate(2. as $ty), 0.);
+                }
+            }
         }
     };
 }
@@ -26,7 +26,10 @@ where
 {
     /// Creates a new kernel density estimator from the `sample`, using a kernel and estimating
     /// the bandwidth using the method `bw`
-    pub fn new(sample: &'a Sample<A>, kernel: K, bw: Bandwidth) -> Kde<'a, A, K> {
+    pub fn new(sample: &'a Sample<A>, kernel: K, bw: Bandwidth) -> Kde<'a, A, K>
+    where
+        usize: cast::From<A, Output = Result<usize, cast::Error>>,
+    {
         Kde {
             bandwidth: bw.estimate(sample),
             kernel,
@@ -39,6 +42,11 @@ where
         self.bandwidth
     }
 
+    /// Returns the sample the density is estimated from
+    pub fn sample(&self) -> &'a Sample<A> {
+        self.sample
+    }
+
     /// Maps the KDE over `xs`
     ///
     /// - Multihreaded
@@ -67,27 +75,196 @@ where
 
         sum / (h * n)
     }
+
+    /// Returns the locations of the modes of the estimated density, in ascending order
+    ///
+    /// The density is evaluated at `npoints` evenly spaced points covering the sample. Local
+    /// maxima lower than a tenth of the highest one are ignored, and so are maxima which are not
+    /// separated from their neighbour by a dip of at least a fifth of the lower of the two; of two
+    /// such maxima, only the higher one is kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `npoints` is less than three
+    pub fn modes(&self, npoints: usize) -> Vec<A> {
+        assert!(npoints > 2);
+
+        let h = self.bandwidth;
+        let start = self.sample.min() - A::cast(3) * h;
+        let end = self.sample.max() + A::cast(3) * h;
+        let step = (end - start) / A::cast(npoints - 1);
+        let xs = (0..npoints)
+            .map(|i| start + step * A::cast(i))
+            .collect::<Vec<_>>();
+        let ys = self.map(&xs);
+
+        let max = ys.iter().fold(A::cast(0), |max, &y| max.max(y));
+        let min_height = max * A::cast(0.1);
+        let max_dip = A::cast(0.8);
+
+        let mut peaks: Vec<usize> = vec![];
+        for i in 1..npoints - 1 {
+            if !(ys[i] > ys[i - 1] && ys[i] >= ys[i + 1] && ys[i] >= min_height) {
+                continue;
+            }
+
+            match peaks.last_mut() {
+                Some(last) => {
+                    let valley = ys[*last..i].iter().fold(ys[i], |min, &y| min.min(y));
+                    if valley < max_dip * ys[*last].min(ys[i]) {
+                        peaks.push(i);
+                    } else if ys[i] > ys[*last] {
+                        *last = i;
+                    }
+                }
+                None => peaks.push(i),
+            }
+        }
+
+        peaks.into_iter().map(|i| xs[i]).collect()
+    }
 }
 
 /// Method to estimate the bandwidth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Bandwidth {
     /// Use Silverman's rule of thumb to estimate the bandwidth from the sample
     Silverman,
+    /// Use Scott's rule of thumb, `sigma * n^(-1/5)`, which smooths slightly less than Silverman's
+    Scott,
+    /// Use the Sheather-Jones "solve-the-equation" plug-in method. Rather than assuming that the
+    /// density is normal, this estimates its curvature from the sample, so it smooths much less
+    /// than the rules of thumb when the sample is multimodal.
+    ///
+    /// Falls back to Silverman's rule if the sample is too small or too sparse.
+    SheatherJones,
 }
 
 impl Bandwidth {
-    fn estimate<A: Float>(self, sample: &Sample<A>) -> A {
+    fn estimate<A: Float>(self, sample: &Sample<A>) -> A
+    where
+        usize: cast::From<A, Output = Result<usize, cast::Error>>,
+    {
+        let n = A::cast(sample.len());
+        let sigma = sample.std_dev(None);
+
         match self {
             Bandwidth::Silverman => {
                 let factor = A::cast(4. / 3.);
                 let exponent = A::cast(1. / 5.);
-                let n = A::cast(sample.len());
-                let sigma = sample.std_dev(None);
 
                 sigma * (factor / n).powf(exponent)
             }
+            Bandwidth::Scott => sigma * n.powf(A::cast(-1. / 5.)),
+            Bandwidth::SheatherJones => {
+                sheather_jones(sample).unwrap_or_else(|| Bandwidth::Silverman.estimate(sample))
+            }
+        }
+    }
+}
+
+// Sheather and Jones (1991), following the implementation of `bw.SJ` in R
+fn sheather_jones<A: Float>(sample: &Sample<A>) -> Option<A>
+where
+    usize: cast::From<A, Output = Result<usize, cast::Error>>,
+{
+    let _0 = A::cast(0);
+    let len = sample.len();
+    if len < 3 {
+        return None;
+    }
+
+    let n = A::cast(len);
+    let (q1, _, q3) = sample.percentiles().quartiles();
+    let scale = sample.std_dev(None).min((q3 - q1) / A::cast(1.349_f32));
+    if scale.is_nan() || scale <= _0 {
+        return None;
+    }
+
+    // Pilot estimates of the integrated squared second and third derivatives of the density
+    let a = A::cast(1.24_f32) * scale * n.powf(A::cast(-1. / 7.));
+    let b = A::cast(1.23_f32) * scale * n.powf(A::cast(-1. / 9.));
+    let td = -phi6(sample, b);
+    if !(td.is_finite() && td > _0) {
+        return None;
+    }
+    let alpha2 = A::cast(1.357_f32) * (phi4(sample, a) / td).powf(A::cast(1. / 7.));
+
+    let c1 = (A::cast(2) * A::cast(std::f32::consts::PI).sqrt() * n).recip();
+    let f =
+        |h: A| (c1 / phi4(sample, alpha2 * h.powf(A::cast(5. / 7.)))).powf(A::cast(1. / 5.)) - h;
+
+    // Find a bracket around the root, widening it like R does
+    let hmax = A::cast(1.144_f32) * scale * n.powf(A::cast(-1. / 5.));
+    let (mut lower, mut upper) = (hmax * A::cast(0.1_f32), hmax);
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    let mut tries = 0;
+    while f_lower * f_upper > _0 {
+        if tries == 100 || !(f_lower.is_finite() && f_upper.is_finite()) {
+            return None;
         }
+        if tries % 2 == 0 {
+            upper = upper * A::cast(1.2_f32);
+            f_upper = f(upper);
+        } else {
+            lower = lower / A::cast(1.2_f32);
+            f_lower = f(lower);
+        }
+        tries += 1;
     }
+
+    // Bisect the bracket
+    for _ in 0..50 {
+        let middle = (lower + upper) / A::cast(2);
+        let f_middle = f(middle);
+        if !f_middle.is_finite() {
+            return None;
+        }
+        if f_middle * f_lower > _0 {
+            lower = middle;
+            f_lower = f_middle;
+        } else {
+            upper = middle;
+        }
+    }
+
+    Some((lower + upper) / A::cast(2))
+}
+
+// Sums `term` of the squared standardized distance between every pair of measurements
+fn pairwise_sum<A: Float>(sample: &Sample<A>, h: A, term: impl Fn(A) -> A) -> A {
+    let mut sum = A::cast(0);
+    for (i, &x) in sample.iter().enumerate() {
+        for &y in &sample[i + 1..] {
+            let delta = ((x - y) / h).powi(2);
+            sum = sum + term(delta);
+        }
+    }
+    sum
+}
+
+// Estimates the integrated squared second derivative of the density, using bandwidth `h`
+fn phi4<A: Float>(sample: &Sample<A>, h: A) -> A {
+    let n = A::cast(sample.len());
+    let sum = pairwise_sum(sample, h, |delta| {
+        (-delta / A::cast(2)).exp() * (delta * delta - A::cast(6) * delta + A::cast(3))
+    });
+    let sum = A::cast(2) * sum + A::cast(3) * n;
+
+    sum / (n * (n - A::cast(1)) * h.powi(5) * A::cast(2. * std::f32::consts::PI).sqrt())
+}
+
+// Estimates the negated integrated squared third derivative of the density, using bandwidth `h`
+fn phi6<A: Float>(sample: &Sample<A>, h: A) -> A {
+    let n = A::cast(sample.len());
+    let sum = pairwise_sum(sample, h, |delta| {
+        (-delta / A::cast(2)).exp()
+            * (delta * delta * delta - A::cast(15) * delta * delta + A::cast(45) * delta
+                - A::cast(15))
+    });
+    let sum = A::cast(2) * sum - A::cast(15) * n;
+
+    sum / (n * (n - A::cast(1)) * h.powi(7) * A::cast(2. * std::f32::consts::PI).sqrt())
 }
 
 #[cfg(test)]
@@ -137,6 +314,61 @@ macro_rules! test {
                     }
                 }
             }
+
+            #[test]
+            fn unimodal() {
+                // The sum of two uniform variables has a triangular distribution
+                let v = (0..100)
+                    .map(|i| ((i % 10) + (i / 10)) as $ty)
+                    .collect::<Vec<_>>();
+                let kde = Kde::new(Sample::new(&v), Gaussian, Bandwidth::Silverman);
+                assert_eq!(kde.modes(500).len(), 1);
+            }
+
+            #[test]
+            fn sheather_jones_smooths_less_on_bimodal_sample() {
+                let v = (0..100)
+                    .map(|i| (i % 10) as $ty / 10. + if i % 2 == 0 { 0. } else { 10. })
+                    .collect::<Vec<_>>();
+                let sample = Sample::new(&v);
+                let silverman = Kde::new(sample, Gaussian, Bandwidth::Silverman).bandwidth();
+                let scott = Kde::new(sample, Gaussian, Bandwidth::Scott).bandwidth();
+                let sheather_jones =
+                    Kde::new(sample, Gaussian, Bandwidth::SheatherJones).bandwidth();
+
+                assert!(scott < silverman);
+                assert!(sheather_jones > 0.);
+                assert!(sheather_jones < silverman / 4.);
+            }
+
+            #[test]
+            fn sheather_jones_on_normal_sample() {
+                // A sample roughly matching the normal quantiles, on which the plug-in estimate
+                // should be close to the normal reference rule
+                let v = (1..200)
+                    .map(|i| {
+                        let p = i as $ty / 200.;
+                        (p / (1. - p)).ln() / 1.7
+                    })
+                    .collect::<Vec<_>>();
+                let sample = Sample::new(&v);
+                let silverman = Kde::new(sample, Gaussian, Bandwidth::Silverman).bandwidth();
+                let sheather_jones =
+                    Kde::new(sample, Gaussian, Bandwidth::SheatherJones).bandwidth();
+
+                assert!(sheather_jones > silverman / 2. && sheather_jones < silverman * 2.);
+            }
+
+            #[test]
+            fn bimodal() {
+                let v = (0..100)
+                    .map(|i| (i % 10) as $ty / 10. + if i % 2 == 0 { 0. } else { 10. })
+                    .collect::<Vec<_>>();
+                let kde = Kde::new(Sample::new(&v), Gaussian, Bandwidth::Silverman);
+                let modes = kde.modes(500);
+                assert_eq!(modes.len(), 2);
+                assert!(modes[0] < 1. && modes[1] > 10.);
+            }
         }
     };
 }
@@ -1,6 +1,7 @@
 //! Mixed bootstrap
 
 use crate::stats::float::Float;
+use crate::stats::rand_util::{new_seed, task_rng};
 use crate::stats::tuple::{Tuple, TupledDistributionsBuilder};
 use crate::stats::univariate::Resamples;
 use crate::stats::univariate::Sample;
@@ -27,6 +28,7 @@ where
     c.extend_from_slice(a);
     c.extend_from_slice(b);
     let c = Sample::new(&c);
+    let seed = new_seed();
 
     #[cfg(feature = "rayon")]
     {
@@ -34,7 +36,8 @@ where
             .into_par_iter()
             .map_init(
                 || Resamples::new(c),
-                |resamples, _| {
+                |resamples, i| {
+                    resamples.set_rng(task_rng(seed, i));
                     let resample = resamples.next();
                     let a: &Sample<A> = Sample::new(&resample[..n_a]);
                     let b: &Sample<A> = Sample::new(&resample[n_a..]);
@@ -62,7 +65,8 @@ where
     {
         let mut resamples = Resamples::new(c);
         (0..nresamples)
-            .map(|_| {
+            .map(|i| {
+                resamples.set_rng(task_rng(seed, i));
                 let resample = resamples.next();
                 let a: &Sample<A> = Sample::new(&resample[..n_a]);
                 let b: &Sample<A> = Sample::new(&resample[n_a..]);
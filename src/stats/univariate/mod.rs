@@ -5,11 +5,16 @@ mod percentiles;
 mod resamples;
 mod sample;
 
+pub mod drift;
+pub mod effect_size;
+pub mod jackknife;
 pub mod kde;
 pub mod mixed;
+pub mod nonparametric;
 pub mod outliers;
 
 use crate::stats::float::Float;
+use crate::stats::rand_util::{new_seed, task_rng};
 use crate::stats::tuple::{Tuple, TupledDistributionsBuilder};
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
@@ -42,6 +47,7 @@ where
 {
     let nresamples_sqrt = (nresamples as f64).sqrt().ceil() as usize;
     let per_chunk = (nresamples + nresamples_sqrt - 1) / nresamples_sqrt;
+    let seed = new_seed();
 
     #[cfg(feature = "rayon")]
     {
@@ -50,6 +56,8 @@ where
             .map_init(
                 || (Resamples::new(a), Resamples::new(b)),
                 |(a_resamples, b_resamples), i| {
+                    a_resamples.set_rng(task_rng(seed, 2 * i));
+                    b_resamples.set_rng(task_rng(seed, 2 * i + 1));
                     let start = i * per_chunk;
                     let end = cmp::min((i + 1) * per_chunk, nresamples);
                     let a_resample = a_resamples.next();
@@ -79,6 +87,8 @@ where
         let mut b_resamples = Resamples::new(b);
         (0..nresamples_sqrt)
             .map(|i| {
+                a_resamples.set_rng(task_rng(seed, 2 * i));
+                b_resamples.set_rng(task_rng(seed, 2 * i + 1));
                 let start = i * per_chunk;
                 let end = cmp::min((i + 1) * per_chunk, nresamples);
                 let a_resample = a_resamples.next();
@@ -0,0 +1,131 @@
+//! Nonparametric two-sample tests
+//!
+//! Unlike the t-test, these tests make no assumption about the shape of the distributions, which
+//! makes them better suited to heavy-tailed or multimodal samples.
+
+use std::cmp::Ordering;
+
+use crate::stats::normal::erfc;
+use crate::stats::rand_util::new_rng;
+use crate::stats::univariate::Sample;
+
+/// Performs a two-sided Mann-Whitney U test, returning the U statistic of `a` and the p-value.
+///
+/// The p-value uses the normal approximation, with corrections for ties and continuity.
+pub fn mann_whitney_u(a: &Sample<f64>, b: &Sample<f64>) -> (f64, f64) {
+    let n_a = a.len();
+    let n_b = b.len();
+    let n = (n_a + n_b) as f64;
+
+    let mut pooled = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect::<Vec<_>>();
+    pooled.sort_unstable_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+
+    // Assign average ranks to ties, and keep track of the tie correction term.
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < pooled.len() {
+        let mut j = i + 1;
+        while j < pooled.len() && pooled[j].0 == pooled[i].0 {
+            j += 1;
+        }
+        let rank = (i + j + 1) as f64 / 2.0;
+        let ties = (j - i) as f64;
+        tie_term += ties * ties * ties - ties;
+        rank_sum_a += rank * pooled[i..j].iter().filter(|x| x.1).count() as f64;
+        i = j;
+    }
+
+    let (n_a, n_b) = (n_a as f64, n_b as f64);
+    let u = rank_sum_a - n_a * (n_a + 1.0) / 2.0;
+    let mean = n_a * n_b / 2.0;
+    let variance = n_a * n_b / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+
+    if variance <= 0.0 {
+        // Every value is the same, so there's no evidence of a difference.
+        return (u, 1.0);
+    }
+
+    let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+    (u, erfc(z / std::f64::consts::SQRT_2).min(1.0))
+}
+
+/// Performs a two-sided permutation test on the difference of the means of `a` and `b`,
+/// returning the p-value.
+///
+/// The p-value is approximated by randomly relabelling the pooled sample `nresamples` times.
+pub fn permutation_test(a: &Sample<f64>, b: &Sample<f64>, nresamples: usize) -> f64 {
+    let n_a = a.len();
+    let observed = (a.mean() - b.mean()).abs();
+
+    let mut pooled = Vec::with_capacity(n_a + b.len());
+    pooled.extend_from_slice(a);
+    pooled.extend_from_slice(b);
+    let total: f64 = pooled.iter().sum();
+
+    let mut rng = new_rng();
+    let mut hits = 0;
+    for _ in 0..nresamples {
+        // A partial Fisher-Yates shuffle is enough to draw a random first group.
+        for i in 0..n_a {
+            let j = rng.rand_range(i as u64..pooled.len() as u64) as usize;
+            pooled.swap(i, j);
+        }
+        let sum_a: f64 = pooled[..n_a].iter().sum();
+        let mean_a = sum_a / n_a as f64;
+        let mean_b = (total - sum_a) / (pooled.len() - n_a) as f64;
+        // Allow for rounding error, so that relabellings which give the same difference as the
+        // observed one count as at least as extreme.
+        if (mean_a - mean_b).abs() >= observed * (1.0 - 1e-12) {
+            hits += 1;
+        }
+    }
+
+    (hits + 1) as f64 / (nresamples + 1) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use super::{mann_whitney_u, permutation_test};
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn mann_whitney_u_statistic() {
+        let a = [1.0, 2.0, 3.0, 4.0];
+        let b = [5.0, 6.0, 7.0, 8.0];
+        let (u, p) = mann_whitney_u(Sample::new(&a), Sample::new(&b));
+        assert_relative_eq!(u, 0.0);
+        assert!(p < 0.05);
+
+        let (u, _) = mann_whitney_u(Sample::new(&b), Sample::new(&a));
+        assert_relative_eq!(u, 16.0);
+    }
+
+    #[test]
+    fn mann_whitney_u_identical_samples() {
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let (_, p) = mann_whitney_u(Sample::new(&a), Sample::new(&a));
+        assert_relative_eq!(p, 1.0);
+
+        let constant = [3.0; 5];
+        let (_, p) = mann_whitney_u(Sample::new(&constant), Sample::new(&constant));
+        assert_relative_eq!(p, 1.0);
+    }
+
+    #[test]
+    fn permutation_test_detects_shift() {
+        let a = (0..30).map(|x| x as f64).collect::<Vec<_>>();
+        let b = (0..30).map(|x| x as f64 + 100.0).collect::<Vec<_>>();
+        let p = permutation_test(Sample::new(&a), Sample::new(&b), 1000);
+        assert!(p < 0.01);
+
+        let p = permutation_test(Sample::new(&a), Sample::new(&a), 1000);
+        assert_relative_eq!(p, 1.0);
+    }
+}
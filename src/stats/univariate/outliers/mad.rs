@@ -0,0 +1,74 @@
+//! Modified z-scores
+//!
+//! The modified z-score of a data point is its distance from the median, in units of the median
+//! absolute deviation (scaled to be consistent with the standard deviation of normally
+//! distributed data). Unlike the standard z-score, it isn't inflated by the outliers it's trying
+//! to find.
+//!
+//! Data points whose modified z-score is larger than the mild threshold are "mild" outliers, and
+//! those larger than the severe threshold are "severe" outliers. Iglewicz and Hoaglin recommend
+//! a threshold of 3.5.
+
+use crate::stats::float::Float;
+use crate::stats::univariate::outliers::tukey::LabeledSample;
+use crate::stats::univariate::Sample;
+
+/// Classifies the sample using the given thresholds of the modified z-score, and returns a
+/// labeled sample.
+///
+/// - Time: `O(N log N) where N = length`
+pub fn classify<A>(sample: &Sample<A>, mild: A, severe: A) -> LabeledSample<'_, A>
+where
+    A: Float,
+    usize: cast::From<A, Output = Result<usize, cast::Error>>,
+{
+    let median = sample.percentiles().median();
+    let mad = sample.median_abs_dev(Some(median));
+
+    // Without any spread every data point that differs from the median at all would be a severe
+    // outlier
+    if mad == A::cast(0_f32) {
+        return LabeledSample::without_outliers(sample);
+    }
+
+    LabeledSample::new(
+        sample,
+        (
+            median - severe * mad,
+            median - mild * mad,
+            median + mild * mad,
+            median + severe * mad,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::classify;
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn finds_spike() {
+        let mut v = (0..100)
+            .map(|i| 100.0 + (i % 10) as f64)
+            .collect::<Vec<_>>();
+        v[50] = 1000.0;
+        v[60] = 120.0;
+
+        let labeled = classify(Sample::new(&v), 3.5, 5.0);
+        assert!(labeled[50].is_severe() && labeled[50].is_high());
+        assert!(labeled[60].is_mild() && labeled[60].is_high());
+        let (los, lom, _, him, his) = labeled.count();
+        assert_eq!((los, lom, him, his), (0, 0, 1, 1));
+    }
+
+    #[test]
+    fn sample_without_spread_has_no_outliers() {
+        let mut v = vec![100.0; 100];
+        v[10] = 101.0;
+        v[20] = 99.0;
+
+        let labeled = classify(Sample::new(&v), 3.5, 5.0);
+        assert_eq!(labeled.count(), (0, 0, 100, 0, 0));
+    }
+}
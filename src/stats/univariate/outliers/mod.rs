@@ -4,4 +4,5 @@
 //! all outlier classifiers are *subjective*, however some classifiers that have become *de facto*
 //! standard are provided here.
 
+pub mod mad;
 pub mod tukey;
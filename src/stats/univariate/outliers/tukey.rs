@@ -66,6 +66,25 @@ impl<'a, A> LabeledSample<'a, A>
 where
     A: Float,
 {
+    /// Labels the sample using the given fences, which are the low severe, low mild, high mild
+    /// and high severe fences in that order
+    pub fn new(sample: &'a Sample<A>, fences: (A, A, A, A)) -> LabeledSample<'a, A> {
+        LabeledSample { fences, sample }
+    }
+
+    /// Labels every data point of the sample as normal
+    pub fn without_outliers(sample: &'a Sample<A>) -> LabeledSample<'a, A> {
+        LabeledSample::new(
+            sample,
+            (
+                A::neg_infinity(),
+                A::neg_infinity(),
+                A::infinity(),
+                A::infinity(),
+            ),
+        )
+    }
+
     /// Returns the number of data points per label
     ///
     /// - Time: `O(length)`
@@ -247,10 +266,11 @@ impl Label {
     }
 }
 
-/// Classifies the sample, and returns a labeled sample.
+/// Classifies the sample using the given multipliers of the interquartile range for the mild
+/// and severe fences, and returns a labeled sample.
 ///
 /// - Time: `O(N log N) where N = length`
-pub fn classify<A>(sample: &Sample<A>) -> LabeledSample<'_, A>
+pub fn classify_with<A>(sample: &Sample<A>, k_mild: A, k_severe: A) -> LabeledSample<'_, A>
 where
     A: Float,
     usize: cast::From<A, Output = Result<usize, cast::Error>>,
@@ -258,18 +278,35 @@ where
     let (q1, _, q3) = sample.percentiles().quartiles();
     let iqr = q3 - q1;
 
-    // Mild
-    let k_m = A::cast(1.5_f32);
-    // Severe
-    let k_s = A::cast(3);
-
-    LabeledSample {
-        fences: (
-            q1 - k_s * iqr,
-            q1 - k_m * iqr,
-            q3 + k_m * iqr,
-            q3 + k_s * iqr,
-        ),
+    // Without any spread the fences would all collapse onto the quartiles, and every data point
+    // that differs from them at all would be a severe outlier
+    if iqr == A::cast(0_f32) {
+        return LabeledSample::without_outliers(sample);
+    }
+
+    LabeledSample::new(
         sample,
+        (
+            q1 - k_severe * iqr,
+            q1 - k_mild * iqr,
+            q3 + k_mild * iqr,
+            q3 + k_severe * iqr,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::classify_with;
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn sample_without_spread_has_no_outliers() {
+        let mut v = vec![100.0; 100];
+        v[10] = 101.0;
+        v[20] = 99.0;
+
+        let labeled = classify_with(Sample::new(&v), 1.5, 3.0);
+        assert_eq!(labeled.count(), (0, 0, 100, 0, 0));
     }
 }
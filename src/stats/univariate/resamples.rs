@@ -28,6 +28,11 @@ where
         }
     }
 
+    /// Replaces the generator used to draw the resamples
+    pub fn set_rng(&mut self, rng: Rng) {
+        self.rng = rng;
+    }
+
     pub fn next(&mut self) -> &Sample<A> {
         let n = self.sample.len();
         let rng = &mut self.rng;
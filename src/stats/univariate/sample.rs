@@ -1,6 +1,7 @@
 use std::{mem, ops};
 
 use crate::stats::float::Float;
+use crate::stats::rand_util::{new_seed, task_rng};
 use crate::stats::tuple::{Tuple, TupledDistributionsBuilder};
 use crate::stats::univariate::Percentiles;
 use crate::stats::univariate::Resamples;
@@ -165,6 +166,29 @@ where
         crate::stats::sum(self)
     }
 
+    /// Returns the mean of the sample after discarding the given proportion of the smallest and of
+    /// the largest elements
+    ///
+    /// At least one element is always kept.
+    ///
+    /// - Time: `O(N log N) where N = length`
+    /// - Memory: `O(length)`
+    pub fn trimmed_mean(&self, proportion: A) -> A
+    where
+        usize: cast::From<A, Output = Result<usize, cast::Error>>,
+    {
+        let n = self.len();
+        let trim = cast::usize((A::cast(n) * proportion).floor())
+            .unwrap()
+            .min((n - 1) / 2);
+        let mut sorted = self.to_vec();
+        // NB This function assumes that there are no `NaN`s in the sample
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let kept = &sorted[trim..n - trim];
+
+        crate::stats::sum(kept) / A::cast(kept.len())
+    }
+
     /// Returns the t score between these two samples
     ///
     /// - Time: `O(length)`
@@ -211,13 +235,17 @@ where
         T::Distributions: Send,
         T::Builder: Send,
     {
+        let seed = new_seed();
         #[cfg(feature = "rayon")]
         {
             (0..nresamples)
                 .into_par_iter()
                 .map_init(
                     || Resamples::new(self),
-                    |resamples, _| statistic(resamples.next()),
+                    |resamples, i| {
+                        resamples.set_rng(task_rng(seed, i));
+                        statistic(resamples.next())
+                    },
                 )
                 .fold(
                     || T::Builder::new(0),
@@ -239,7 +267,10 @@ where
         {
             let mut resamples = Resamples::new(self);
             (0..nresamples)
-                .map(|_| statistic(resamples.next()))
+                .map(|i| {
+                    resamples.set_rng(task_rng(seed, i));
+                    statistic(resamples.next())
+                })
                 .fold(T::Builder::new(0), |mut sub_distributions, sample| {
                     sub_distributions.push(sample);
                     sub_distributions
@@ -272,3 +303,24 @@ impl<A> ops::Deref for Sample<A> {
         &self.0
     }
 }
+
+#[cfg(test)]
+mod test {
+    use approx::assert_relative_eq;
+
+    use crate::stats::univariate::Sample;
+
+    #[test]
+    fn trimmed_mean_discards_extremes() {
+        let v = [1000.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, -1000.0];
+        let sample = Sample::new(&v);
+        assert_relative_eq!(sample.trimmed_mean(0.1), 5.5);
+        assert_relative_eq!(sample.trimmed_mean(0.0), sample.mean());
+    }
+
+    #[test]
+    fn trimmed_mean_keeps_one_element() {
+        let v = [1.0, 2.0, 30.0];
+        assert_relative_eq!(Sample::new(&v).trimmed_mean(0.5), 2.0);
+    }
+}
@@ -1,15 +1,18 @@
-#[cfg(feature = "plotters")]
-use criterion::SamplingMode;
 use criterion::{
-    criterion_group, criterion_main, profiler::Profiler, BatchSize, BenchmarkId, Criterion,
+    criterion_group, criterion_main, profiler::Profiler, BatchSize, BenchmarkId, ComparisonReport,
+    ComparisonTest, ConfidenceIntervalMethod, Criterion, MultipleComparisonCorrection,
+    OutlierDetection, RegressionModel, TrackedStatistic,
 };
+#[cfg(feature = "plotters")]
+use criterion::{KdeBandwidth, KdeKernel, PlotConfiguration, SamplingMode};
 use serde_json::value::Value;
 use std::cell::{Cell, RefCell};
 use std::cmp::max;
 use std::fs::File;
+use std::hint::black_box;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 use tempfile::{tempdir, TempDir};
 use walkdir::WalkDir;
 
@@ -230,6 +233,27 @@ fn test_warmup_time() {
     assert!(counter1.read() < counter2.read());
 }
 
+#[test]
+fn test_adaptive_warm_up_ends_early() {
+    let dir = temp_dir();
+    let start = Instant::now();
+    short_benchmark(&dir)
+        .warm_up_time(Duration::from_secs(30))
+        .adaptive_warm_up(true)
+        .bench_function("test_adaptive_warm_up", |b| {
+            // Each iteration spins until its share of the batch is up, so the batches take the
+            // same time however busy the machine is
+            b.iter_custom(|iters| {
+                let start = Instant::now();
+                let end = start + Duration::from_micros(iters);
+                while Instant::now() < end {}
+                start.elapsed()
+            })
+        });
+
+    assert!(start.elapsed() < Duration::from_secs(15));
+}
+
 #[test]
 fn test_measurement_time() {
     let dir = temp_dir();
@@ -249,6 +273,480 @@ fn test_measurement_time() {
     assert!(counter1.read() < counter2.read());
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pin_to_cpu_is_recorded() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .pin_to_cpu(0)
+        .bench_function("test_pin_to_cpu", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(dir.path(), "test_pin_to_cpu/new/sample.json")).unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(sample["cpu_affinity"], 0);
+}
+
+#[test]
+fn test_shuffle_seed_is_recorded() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .shuffle(Some(42))
+        .bench_function("test_shuffle_seed", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(dir.path(), "test_shuffle_seed/new/sample.json")).unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(sample["shuffle_seed"], 42);
+}
+
+fn shuffled_target_a(c: &mut Criterion) {
+    c.bench_function("shuffled_a", |b| b.iter(|| 10));
+}
+
+fn shuffled_target_b(c: &mut Criterion) {
+    c.bench_function("shuffled_b", |b| b.iter(|| 20));
+}
+
+#[test]
+fn test_shuffled_targets_all_run() {
+    let dir = temp_dir();
+    let mut c = short_benchmark(&dir).shuffle(Some(7));
+    c.run_targets(&mut [shuffled_target_a, shuffled_target_b]);
+
+    verify_stats(dir.path(), "shuffled_a/base");
+    verify_stats(dir.path(), "shuffled_b/base");
+}
+
+#[test]
+fn test_shuffled_group_members() {
+    let dir = temp_dir();
+    let order = RefCell::new(vec![]);
+    let mut c = short_benchmark(&dir)
+        .warm_up_time(Duration::from_millis(50))
+        .measurement_time(Duration::from_millis(100))
+        .sample_size(10)
+        .without_plots()
+        .shuffle(Some(7));
+    let mut group = c.benchmark_group("shuffled_group");
+    let mut shuffled = group.shuffled();
+    for i in 0..8 {
+        let order = &order;
+        shuffled.bench_with_input(BenchmarkId::from_parameter(i), i, move |b, &i| {
+            if order.borrow().last() != Some(&i) {
+                order.borrow_mut().push(i);
+            }
+            b.iter(|| i)
+        });
+    }
+    // Nothing runs until the set is finished
+    assert!(order.borrow().is_empty());
+    shuffled.finish();
+    group.finish();
+
+    let order = order.into_inner();
+    let mut sorted = order.clone();
+    sorted.sort_unstable();
+    assert_eq!((0..8).collect::<Vec<_>>(), sorted);
+    assert_ne!(sorted, order);
+}
+
+#[test]
+fn test_repetitions() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .sample_size(10)
+        .repetitions(2)
+        .bench_function("test_repetitions", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(dir.path(), "test_repetitions/new/sample.json")).unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(sample["run_lengths"], serde_json::json!([10, 10]));
+    assert_eq!(sample["times"].as_array().unwrap().len(), 20);
+
+    let f = File::open(verify_file(dir.path(), "test_repetitions/new/runs.json")).unwrap();
+    let runs: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(runs.as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_nonparametric_comparison_tests() {
+    let dir = temp_dir();
+    short_benchmark(&dir).bench_function("test_nonparametric", |b| b.iter(|| 10));
+    for (test, name) in [
+        (ComparisonTest::TTest, "TTest"),
+        (ComparisonTest::MannWhitneyU, "MannWhitneyU"),
+        (ComparisonTest::Permutation, "Permutation"),
+    ] {
+        short_benchmark(&dir)
+            .nresamples(1000)
+            .comparison_test(test)
+            .bench_function("test_nonparametric", |b| b.iter(|| 10));
+
+        let path = verify_file(dir.path(), "test_nonparametric/change/estimates.json");
+        let f = File::open(path).unwrap();
+        let estimates: Value = serde_json::from_reader(f).unwrap();
+        assert_eq!(estimates["test"]["test"], name);
+        let p_value = estimates["test"]["p_value"].as_f64().unwrap();
+        assert!((0.0..=1.0).contains(&p_value));
+    }
+}
+
+#[test]
+fn test_multiple_comparison_correction() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        let mut c = short_benchmark(&dir)
+            .nresamples(1000)
+            .multiple_comparison_correction(MultipleComparisonCorrection::Holm);
+        let mut group = c.benchmark_group("test_correction");
+        group.bench_function("1", |b| b.iter(|| 10));
+        group.bench_function("2", |b| b.iter(|| 20));
+        group.finish();
+        c.bench_function("test_correction_alone", |b| b.iter(|| 30));
+        c.final_summary();
+    }
+
+    // The benchmark outside of the group is corrected along with the rest of the run
+    let mut tests = [
+        "test_correction/1",
+        "test_correction/2",
+        "test_correction_alone",
+    ]
+    .map(|id| {
+        let path = verify_file(dir.path(), &format!("{}/change/estimates.json", id));
+        let estimates: Value = serde_json::from_reader(File::open(path).unwrap()).unwrap();
+        assert_eq!(estimates["test"]["correction"], "Holm");
+        let p_value = estimates["test"]["p_value"].as_f64().unwrap();
+        let adjusted = estimates["test"]["adjusted_p_value"].as_f64().unwrap();
+        (p_value, adjusted)
+    });
+
+    // With three comparisons, Holm's method multiplies the smallest p-value by three and the next
+    // by two, and none is adjusted to less than a smaller one was
+    tests.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut expected = 0.0_f64;
+    for (rank, (p_value, adjusted)) in tests.into_iter().enumerate() {
+        expected = expected.max(((3 - rank) as f64 * p_value).min(1.0));
+        assert!((adjusted - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_effect_sizes_are_saved() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        short_benchmark(&dir)
+            .nresamples(1000)
+            .bench_function("test_effect_sizes", |b| b.iter(|| 10));
+    }
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_effect_sizes/change/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    let effect_sizes = &estimates["effect_sizes"];
+    assert!(effect_sizes["cohens_d"].is_f64());
+    assert!(effect_sizes["hodges_lehmann"].is_f64());
+    let superiority = effect_sizes["probability_of_superiority"].as_f64().unwrap();
+    assert!((0.0..=1.0).contains(&superiority));
+}
+
+#[test]
+fn test_exclude_severe_outliers() {
+    let dir = temp_dir();
+    let calls = Cell::new(0u64);
+    short_benchmark(&dir)
+        .exclude_severe_outliers(true)
+        .bench_function("test_exclude_severe_outliers", |b| {
+            b.iter_custom(|iters| {
+                let start = Instant::now();
+                for i in 0..iters {
+                    black_box(i);
+                }
+                let elapsed = start.elapsed();
+
+                // Make every tenth measurement a severe outlier
+                calls.set(calls.get() + 1);
+                if calls.get() % 10 == 0 {
+                    elapsed * 100
+                } else {
+                    elapsed
+                }
+            })
+        });
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_exclude_severe_outliers/new/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    let excluded = estimates["excluded_outliers"].as_u64().unwrap();
+    assert!(excluded >= 5);
+
+    // The saved sample still includes the outliers
+    assert_eq!(
+        100,
+        sample_count(dir.path(), "test_exclude_severe_outliers/new/sample.json")
+    );
+}
+
+#[test]
+fn test_outlier_detection_none() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .outlier_detection(OutlierDetection::None)
+        .exclude_severe_outliers(true)
+        .bench_function("test_outlier_detection_none", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_outlier_detection_none/new/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(Some(0), estimates["excluded_outliers"].as_u64());
+}
+
+#[test]
+fn test_bca_confidence_intervals() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        short_benchmark(&dir)
+            .confidence_interval_method(ConfidenceIntervalMethod::Bca)
+            .bench_function("test_bca", |b| b.iter(|| 10));
+    }
+
+    for path in [
+        "test_bca/new/estimates.json",
+        "test_bca/change/estimates.json",
+    ] {
+        let f = File::open(verify_file(dir.path(), path)).unwrap();
+        let estimates: Value = serde_json::from_reader(f).unwrap();
+        let interval = &estimates["mean"]["confidence_interval"];
+        assert_eq!(interval["method"], "Bca");
+        assert!(
+            interval["lower_bound"].as_f64().unwrap() <= interval["upper_bound"].as_f64().unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_change_probabilities() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        short_benchmark(&dir)
+            .comparison_report(ComparisonReport::Both)
+            .noise_threshold(0.02)
+            .bench_function("test_probabilities", |b| b.iter(|| 10));
+    }
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_probabilities/change/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    let probabilities = &estimates["probabilities"];
+    assert_eq!(probabilities["noise_threshold"], 0.02);
+    let regression = probabilities["regression"].as_f64().unwrap();
+    let improvement = probabilities["improvement"].as_f64().unwrap();
+    assert!((0.0..=1.0).contains(&regression));
+    assert!((0.0..=1.0).contains(&improvement));
+    assert!(regression + improvement <= 1.0);
+}
+
+#[test]
+fn test_equivalence() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        short_benchmark(&dir)
+            .equivalence_margin(1.0)
+            .bench_function("test_equivalence", |b| b.iter(|| 10));
+    }
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_equivalence/change/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    let equivalence = &estimates["equivalence"];
+    assert_eq!(equivalence["margin"], 1.0);
+    // The same function is well within ±100% of itself
+    assert_eq!(equivalence["equivalent"], true);
+    assert!(equivalence["p_value"].as_f64().unwrap() < 0.05);
+}
+
+#[test]
+fn test_noise_history() {
+    let dir = temp_dir();
+    for _ in 0..7 {
+        short_benchmark(&dir)
+            .learn_noise_threshold(true)
+            .bench_function("test_noise", |b| b.iter(|| 10));
+    }
+
+    let recorded = || {
+        let f = File::open(verify_file(dir.path(), "test_noise/noise_history.json")).unwrap();
+        let history: Value = serde_json::from_reader(f).unwrap();
+        history["changes"]["mean"].as_array().unwrap().len()
+    };
+    // The first run has no baseline to compare against
+    assert_eq!(recorded(), 6);
+
+    // Comparisons against a fixed baseline aren't changes between consecutive runs
+    short_benchmark(&dir)
+        .retain_baseline("base".to_owned(), true)
+        .bench_function("test_noise", |b| b.iter(|| 10));
+    assert_eq!(recorded(), 6);
+}
+
+#[test]
+fn test_power_analysis() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .target_effect(0.005)
+        .bench_function("test_power", |b| b.iter(|| 10));
+
+    #[cfg(feature = "html_reports")]
+    {
+        let report = verify_file(dir.path(), "test_power/report/index.html");
+        let html = std::fs::read_to_string(report).unwrap();
+        assert!(html.contains("Minimum detectable change"));
+        assert!(html.contains("Sample size to detect ±0.50%"));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_target_effect_must_be_positive() {
+    let _ = Criterion::default().target_effect(0.0);
+}
+
+#[test]
+fn test_tracked_statistic() {
+    let dir = temp_dir();
+    for _ in 0..2 {
+        short_benchmark(&dir)
+            .tracked_statistic(TrackedStatistic::Percentile(90))
+            .additional_statistics(&[TrackedStatistic::Min, TrackedStatistic::TrimmedMean])
+            .comparison_report(ComparisonReport::Probability)
+            .bench_function("test_tracked", |b| b.iter(|| 10));
+    }
+
+    let f = File::open(verify_file(dir.path(), "test_tracked/new/estimates.json")).unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    for estimate in [
+        &estimates["min"],
+        &estimates["trimmed_mean"],
+        &estimates["percentiles"]["90"],
+    ] {
+        let ci = &estimate["confidence_interval"];
+        let point = estimate["point_estimate"].as_f64().unwrap();
+        assert!(ci["lower_bound"].as_f64().unwrap() <= point);
+        assert!(point <= ci["upper_bound"].as_f64().unwrap());
+    }
+    assert!(
+        estimates["min"]["point_estimate"].as_f64().unwrap()
+            <= estimates["percentiles"]["90"]["point_estimate"]
+                .as_f64()
+                .unwrap()
+    );
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_tracked/change/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    assert!(estimates["percentiles"]["90"].is_object());
+    assert!(estimates["min"].is_object());
+    assert!(estimates["probabilities"].is_object());
+    // The p90 is tested with its bootstrap distribution rather than the comparison test
+    assert_eq!(estimates["test"]["statistic"]["Percentile"], 90);
+    assert!(estimates["test"]["test"].is_null());
+    let p_value = estimates["test"]["p_value"].as_f64().unwrap();
+    assert!((0.0..=1.0).contains(&p_value));
+
+    #[cfg(feature = "html_reports")]
+    {
+        let report = verify_file(dir.path(), "test_tracked/report/index.html");
+        let html = std::fs::read_to_string(report).unwrap();
+        assert!(html.contains("Trimmed mean"));
+        assert!(html.contains("Change in p90"));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_tracked_percentile_out_of_range() {
+    let _ = Criterion::default().tracked_statistic(TrackedStatistic::Percentile(100));
+}
+
+#[test]
+fn test_rng_seed_is_recorded() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .rng_seed(42)
+        .bench_function("test_seed", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(dir.path(), "test_seed/new/estimates.json")).unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(estimates["rng_seed"], 42);
+}
+
+#[test]
+fn test_regression_with_intercept() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .regression_model(RegressionModel::WithIntercept)
+        .bench_function("test_intercept", |b| b.iter(|| 10));
+
+    let f = File::open(verify_file(dir.path(), "test_intercept/new/estimates.json")).unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    assert_eq!(estimates["regression_model"], "WithIntercept");
+    assert!(estimates["intercept"]["point_estimate"].is_f64());
+    assert!(estimates["slope_with_intercept"]["point_estimate"].is_f64());
+}
+
+fn sample_count(dir: &Path, path: &str) -> usize {
+    let f = File::open(verify_file(dir, path)).unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    sample["times"].as_array().unwrap().len()
+}
+
+#[test]
+fn test_target_precision_stops_when_reached() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .sample_size(10)
+        .target_precision(1.0)
+        .bench_function("test_target_precision_reached", |b| b.iter(|| 10));
+
+    assert_eq!(
+        10,
+        sample_count(dir.path(), "test_target_precision_reached/new/sample.json")
+    );
+}
+
+#[test]
+fn test_target_precision_stops_at_measurement_time() {
+    let dir = temp_dir();
+    short_benchmark(&dir)
+        .sample_size(10)
+        .target_precision(1e-12)
+        .bench_function("test_target_precision_unreachable", |b| b.iter(|| 10));
+
+    let count = sample_count(
+        dir.path(),
+        "test_target_precision_unreachable/new/sample.json",
+    );
+    assert!(count > 10);
+    assert_eq!(0, count % 10);
+}
+
 #[test]
 fn test_bench_function() {
     let dir = temp_dir();
@@ -349,6 +847,7 @@ fn test_output_files() {
             verify_svg(&dir, "report/mean.svg");
             verify_svg(&dir, "report/median.svg");
             verify_svg(&dir, "report/pdf.svg");
+            verify_svg(&dir, "report/iteration_order.svg");
             verify_svg(&dir, "report/regression.svg");
             verify_svg(&dir, "report/SD.svg");
             verify_svg(&dir, "report/slope.svg");
@@ -386,6 +885,88 @@ fn test_output_files() {
     }
 }
 
+#[cfg(feature = "plotters")]
+#[test]
+fn test_output_files_kde_configuration() {
+    let tempdir = temp_dir();
+    // Run benchmark twice to produce comparisons
+    for _ in 0..2 {
+        let mut c = short_benchmark(&tempdir);
+        let mut group = c.benchmark_group("test_output");
+        group.plot_config(
+            PlotConfiguration::default()
+                .kde_kernel(KdeKernel::Epanechnikov)
+                .kde_bandwidth(KdeBandwidth::SheatherJones),
+        );
+        group.bench_function("output_kde", |b| b.iter(|| 10));
+    }
+
+    let dir = tempdir.path().join("test_output/output_kde");
+
+    verify_stats(&dir, "new");
+
+    #[cfg(feature = "html_reports")]
+    {
+        verify_svg(&dir, "report/pdf.svg");
+        verify_svg(&dir, "report/both/pdf.svg");
+        verify_svg(&dir, "report/pdf_small.svg");
+        verify_svg(&dir, "report/relative_pdf_small.svg");
+    }
+}
+
+#[cfg(feature = "plotters")]
+#[test]
+fn test_output_files_complexity() {
+    let tempdir = temp_dir();
+    let mut c = short_benchmark(&tempdir);
+    let mut group = c.benchmark_group("test_complexity");
+    for size in [1000u64, 2000, 4000, 8000] {
+        group.bench_with_input(BenchmarkId::new("sum", size), &size, |b, &size| {
+            b.iter(|| (0..black_box(size)).sum::<u64>())
+        });
+    }
+    group.finish();
+
+    #[cfg(feature = "html_reports")]
+    {
+        let dir = tempdir.path().join("test_complexity");
+        for plot in ["report/lines.svg", "sum/report/lines.svg"] {
+            let svg = std::fs::read_to_string(verify_file(&dir, plot)).unwrap();
+            assert!(svg.contains("sum: O("));
+            assert!(svg.contains("(R² = "));
+            assert!(!svg.contains("NaN"));
+        }
+    }
+}
+
+#[cfg(feature = "plotters")]
+#[test]
+fn test_output_files_reference() {
+    let tempdir = temp_dir();
+    let mut c = short_benchmark(&tempdir);
+    let mut group = c.benchmark_group("test_reference");
+    group.reference("std");
+    for size in [1000u64, 4000] {
+        group.bench_with_input(BenchmarkId::new("std", size), &size, |b, &size| {
+            b.iter(|| (0..black_box(size)).sum::<u64>())
+        });
+        group.bench_with_input(BenchmarkId::new("fold", size), &size, |b, &size| {
+            b.iter(|| (0..black_box(size)).fold(0u64, |a, b| a.wrapping_add(b)))
+        });
+    }
+    group.finish();
+
+    #[cfg(feature = "html_reports")]
+    {
+        let dir = tempdir.path().join("test_reference");
+        for page in ["report/index.html", "4000/report/index.html"] {
+            let html = std::fs::read_to_string(verify_file(&dir, page)).unwrap();
+            assert!(html.contains("Relative Performance"));
+            assert!(html.contains(" than std"));
+        }
+    }
+}
+
 #[cfg(feature = "plotters")]
 #[test]
 fn test_output_files_flat_sampling() {
@@ -410,6 +991,7 @@ fn test_output_files_flat_sampling() {
         verify_svg(&dir, "report/mean.svg");
         verify_svg(&dir, "report/median.svg");
         verify_svg(&dir, "report/pdf.svg");
+        verify_svg(&dir, "report/iteration_order.svg");
         verify_svg(&dir, "report/iteration_times.svg");
         verify_svg(&dir, "report/SD.svg");
         verify_svg(&dir, "report/typical.svg");
@@ -427,6 +1009,79 @@ fn test_output_files_flat_sampling() {
     }
 }
 
+#[test]
+fn test_custom_sampling_schedule() {
+    let dir = temp_dir();
+    let mut c = short_benchmark(&dir);
+    let mut group = c.benchmark_group("test_sampling");
+    group.sampling_mode(SamplingMode::Custom(vec![5, 1, 20, 3, 8, 13, 2, 40, 7, 10]));
+    group.bench_function("custom", |b| b.iter(|| 10));
+    group.finish();
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_sampling/custom/new/sample.json",
+    ))
+    .unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    let iters = sample["iters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x.as_f64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        iters,
+        vec![5.0, 1.0, 20.0, 3.0, 8.0, 13.0, 2.0, 40.0, 7.0, 10.0]
+    );
+    verify_json(dir.path(), "test_sampling/custom/new/estimates.json");
+}
+
+#[test]
+fn test_geometric_sampling() {
+    let dir = temp_dir();
+    let mut c = short_benchmark(&dir);
+    let mut group = c.benchmark_group("test_sampling");
+    group.sampling_mode(SamplingMode::Geometric);
+    group.bench_function("geometric", |b| b.iter(|| 10));
+    group.finish();
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_sampling/geometric/new/sample.json",
+    ))
+    .unwrap();
+    let sample: Value = serde_json::from_reader(f).unwrap();
+    let iters = sample["iters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x.as_f64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(iters.len(), 100);
+    assert!(iters.windows(2).all(|w| w[0] <= w[1]));
+    // Spread over roughly the same range as linear sampling.
+    let span = iters[iters.len() - 1] / iters[0];
+    assert!(span > 50.0 && span < 200.0, "span was {}", span);
+
+    let f = File::open(verify_file(
+        dir.path(),
+        "test_sampling/geometric/new/estimates.json",
+    ))
+    .unwrap();
+    let estimates: Value = serde_json::from_reader(f).unwrap();
+    assert!(!estimates["slope"].is_null());
+}
+
+#[test]
+#[should_panic(expected = "at least two iteration counts")]
+fn test_custom_sampling_schedule_too_short() {
+    let dir = temp_dir();
+    let mut c = short_benchmark(&dir);
+    let mut group = c.benchmark_group("test_sampling");
+    group.sampling_mode(SamplingMode::Custom(vec![5]));
+}
+
 #[test]
 #[should_panic(expected = "Benchmark function must call Bencher::iter or related method.")]
 fn test_bench_with_no_iteration_panics() {
@@ -456,6 +1111,30 @@ fn test_benchmark_group_without_input() {
     group.finish();
 }
 
+#[test]
+fn test_benchmark_group_interleaved() {
+    let dir = temp_dir();
+    let mut c = short_benchmark(&dir);
+    let mut group = c.benchmark_group("Interleaved");
+    let counter1 = Counter::default();
+    let counter2 = Counter::default();
+    let clone1 = counter1.clone();
+    let clone2 = counter2.clone();
+    group
+        .interleaved()
+        .bench_function("A", move |b| b.iter(|| clone1.count()))
+        .bench_function("B", move |b| b.iter(|| clone2.count()))
+        .finish();
+    group.finish();
+
+    assert!(counter1.read() > 0);
+    assert!(counter2.read() > 0);
+    verify_stats(dir.path(), "Interleaved/A/base");
+    verify_stats(dir.path(), "Interleaved/B/base");
+    verify_json(dir.path(), "Interleaved/B/new/paired.json");
+    verify_not_exists(dir.path(), "Interleaved/A/new/paired.json");
+}
+
 #[test]
 fn test_criterion_doesnt_panic_if_measured_time_is_zero() {
     let dir = temp_dir();
@@ -465,6 +1144,38 @@ fn test_criterion_doesnt_panic_if_measured_time_is_zero() {
     });
 }
 
+// Refusing to run exits the process, so the benchmark is run in a child process: this test binary
+// again, running only this test. Test builds have debug assertions enabled, which is one of the
+// problems strict checking refuses to run with.
+#[cfg(debug_assertions)]
+#[test]
+fn test_strict_environment_refuses_noisy_system() {
+    const CHILD: &str = "CRITERION_TEST_STRICT_ENV_CHILD";
+    if let Some(dir) = std::env::var_os(CHILD) {
+        Criterion::default()
+            .output_directory(Path::new(&dir))
+            .strict_environment(true)
+            .bench_function("strict_env", |b| b.iter(|| 10));
+        return;
+    }
+
+    let dir = temp_dir();
+    let output = std::process::Command::new(std::env::current_exe().unwrap())
+        .args([
+            "test_strict_environment_refuses_noisy_system",
+            "--exact",
+            "--nocapture",
+        ])
+        .env(CHILD, dir.path())
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(Some(1), output.status.code(), "{}", stderr);
+    assert!(stderr.contains("refusing to run benchmarks in a noisy environment"));
+    assert!(stderr.contains("debug assertions"));
+    verify_not_exists(dir.path(), "strict_env/new/sample.json");
+}
+
 mod macros {
     use super::{criterion_group, criterion_main, Criterion};
 